@@ -0,0 +1,90 @@
+use std::future::Future;
+
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+
+use crate::{ApiClient, ApiError, Endpoint, GeoNamesApi, GeoNamesRequest, Params};
+
+pub type BatchItem<I, T> = (I, Result<T, ApiError>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    pub concurrency: usize,
+    pub ordered: bool,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            concurrency: 8,
+            ordered: false,
+        }
+    }
+}
+
+impl BatchOptions {
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    // Yield results in input order instead of completion order
+    pub fn ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+}
+
+// Every call goes through `call`, so validation, caching, credits and retries apply per input
+impl ApiClient {
+    pub fn batch<'a, T, I>(
+        &'a self,
+        api: GeoNamesApi,
+        inputs: I,
+        options: BatchOptions,
+    ) -> BoxStream<'a, BatchItem<Params, T>>
+    where
+        T: DeserializeOwned + Send + 'a,
+        I: IntoIterator<Item = Params>,
+        I::IntoIter: Send + 'a,
+    {
+        let calls = stream::iter(inputs).map(move |params| async move {
+            let result = self.call(api, Some(params.clone())).await;
+            (params, result)
+        });
+
+        buffer(calls, options)
+    }
+
+    pub fn send_batch<'a, R, I>(
+        &'a self,
+        requests: I,
+        options: BatchOptions,
+    ) -> BoxStream<'a, BatchItem<R, <R::Endpoint as Endpoint>::Response>>
+    where
+        R: GeoNamesRequest + Clone + Send + 'a,
+        <R::Endpoint as Endpoint>::Response: Send,
+        I: IntoIterator<Item = R>,
+        I::IntoIter: Send + 'a,
+    {
+        let calls = stream::iter(requests).map(move |request| async move {
+            let result = self.send(request.clone()).await;
+            (request, result)
+        });
+
+        buffer(calls, options)
+    }
+}
+
+fn buffer<'a, S>(calls: S, options: BatchOptions) -> BoxStream<'a, <S::Item as Future>::Output>
+where
+    S: Stream + Send + 'a,
+    S::Item: Future + Send,
+    <S::Item as Future>::Output: Send,
+{
+    if options.ordered {
+        calls.buffered(options.concurrency.max(1)).boxed()
+    } else {
+        calls.buffer_unordered(options.concurrency.max(1)).boxed()
+    }
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+use std::time::Duration;
+
+use bytes::Bytes;
+use reqwest::blocking::Client;
+use reqwest::Proxy;
+use serde::de::DeserializeOwned;
+
+use crate::client::{
+    default_base_url, deserialize, http_status_error, no_host_error, normalize_base_url,
+    read_response, request_url, validate_params, DEFAULT_USER_AGENT,
+};
+use crate::redact::{Redactor, REDACTED};
+use crate::{
+    ApiError, Endpoint, GeoNamesApi, GeoNamesRequest, Params, RequestContext, RequestError,
+    RetryPolicy, TransportResponse,
+};
+
+// Synchronous counterpart of `crate::ApiClient`, backed by reqwest's blocking client
+#[derive(Clone)]
+pub struct ApiClient {
+    http: Client,
+    username: String,
+    token: Option<String>,
+    base_url: String,
+    fallback_hosts: Vec<String>,
+    retry_policy: RetryPolicy,
+    redactor: Redactor,
+}
+
+impl ApiClient {
+    pub fn new(username: impl Into<String>, token: Option<&str>) -> Self {
+        let mut builder = ApiClientBuilder::new(username);
+        if let Some(token) = token {
+            builder = builder.token(token);
+        }
+
+        builder
+            .build()
+            .expect("the default client configuration is valid")
+    }
+
+    pub fn builder(username: impl Into<String>) -> ApiClientBuilder {
+        ApiClientBuilder::new(username)
+    }
+
+    pub fn call<T: DeserializeOwned>(
+        &self,
+        api: GeoNamesApi,
+        params: Option<Params>,
+    ) -> Result<T, ApiError> {
+        self.execute(api, params)
+            .map_err(|e| e.redact(&self.redactor))
+    }
+
+    pub fn call_endpoint<E: Endpoint>(
+        &self,
+        params: Option<Params>,
+    ) -> Result<E::Response, ApiError> {
+        self.call(E::API, params)
+    }
+
+    pub fn send<R: GeoNamesRequest>(
+        &self,
+        request: R,
+    ) -> Result<<R::Endpoint as Endpoint>::Response, ApiError> {
+        self.call_endpoint::<R::Endpoint>(request.into_params())
+    }
+
+    fn execute<T: DeserializeOwned>(
+        &self,
+        api: GeoNamesApi,
+        params: Option<Params>,
+    ) -> Result<T, ApiError> {
+        validate_params(api, params.as_ref())?;
+
+        let mut attempt = 1;
+        let (body, context) = loop {
+            match self.fetch(api, params.as_ref()) {
+                Err(error) if self.retry_policy.should_retry(&error, attempt) => {
+                    std::thread::sleep(self.retry_policy.delay(attempt));
+                    attempt += 1;
+                }
+                result => break result?,
+            }
+        };
+
+        deserialize(&body).map_err(|e| e.with_context(context))
+    }
+
+    // Returns the body with the context of the request that served it
+    fn fetch(
+        &self,
+        api: GeoNamesApi,
+        params: Option<&Params>,
+    ) -> Result<(Bytes, RequestContext), ApiError> {
+        let mut last_error = None;
+
+        for host in std::iter::once(&self.base_url).chain(self.fallback_hosts.iter()) {
+            let url = request_url(host, api, params, &self.username, self.token.as_deref())?;
+            let context = RequestContext::new(api, &url);
+
+            match self.get(url) {
+                Ok(response) if response.status >= 500 => {
+                    last_error = Some(http_status_error(response, context));
+                }
+                Ok(response) => {
+                    let status = response.status;
+                    let body = read_response(response, context.clone())?;
+                    return Ok((body, context.with_http_status(status)));
+                }
+                Err(error) => last_error = Some(error.with_context(context)),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(no_host_error))
+    }
+
+    fn get(&self, url: reqwest::Url) -> Result<TransportResponse, ApiError> {
+        let response = self.http.get(url).send().map_err(|e| {
+            ApiError::Connection(
+                RequestError::new("Geonames api error").with_source(e.without_url()),
+            )
+        })?;
+
+        let status = response.status().as_u16();
+        let body = response.bytes().map_err(|e| {
+            ApiError::Connection(
+                RequestError::new("Failed to read response body").with_source(e.without_url()),
+            )
+        })?;
+
+        Ok(TransportResponse { status, body })
+    }
+}
+
+impl fmt::Debug for ApiClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiClient")
+            .field("username", &REDACTED)
+            .field("token", &self.token.as_ref().map(|_| REDACTED))
+            .field("base_url", &self.base_url)
+            .field("fallback_hosts", &self.fallback_hosts)
+            .field("retry_policy", &self.retry_policy)
+            .finish()
+    }
+}
+
+pub struct ApiClientBuilder {
+    username: String,
+    token: Option<String>,
+    base_url: Option<String>,
+    fallback_hosts: Vec<String>,
+    http_client: Option<Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: String,
+    gzip: bool,
+    retry_policy: RetryPolicy,
+}
+
+impl ApiClientBuilder {
+    pub fn new(username: impl Into<String>) -> Self {
+        ApiClientBuilder {
+            username: username.into(),
+            token: None,
+            base_url: None,
+            fallback_hosts: vec![],
+            http_client: None,
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            gzip: true,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    pub fn fallback_host(mut self, host: impl Into<String>) -> Self {
+        self.fallback_hosts.push(host.into());
+        self
+    }
+
+    pub fn http_client(mut self, http_client: Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn build(self) -> Result<ApiClient, ApiError> {
+        let base_url = match self.base_url {
+            Some(base_url) => normalize_base_url(base_url)?,
+            None => default_base_url(self.token.as_deref()),
+        };
+
+        let fallback_hosts = self
+            .fallback_hosts
+            .into_iter()
+            .map(normalize_base_url)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let http = match self.http_client {
+            Some(http) => http,
+            None => {
+                let mut builder = Client::builder()
+                    .user_agent(self.user_agent)
+                    .gzip(self.gzip);
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+
+                builder.build().map_err(|e| {
+                    ApiError::GeonamesApi(format!("Failed to build HTTP client: {}", e))
+                })?
+            }
+        };
+
+        let redactor = Redactor::new(&self.username, self.token.as_deref());
+
+        Ok(ApiClient {
+            http,
+            username: self.username,
+            token: self.token,
+            base_url,
+            fallback_hosts,
+            retry_policy: self.retry_policy,
+            redactor,
+        })
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+
+use crate::redact::form_urlencode;
+use crate::{GeoNamesApi, Params};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheConfig {
+    pub capacity: usize,
+    pub default_ttl: Duration,
+    pub ttls: HashMap<GeoNamesApi, Duration>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig::new(1000)
+    }
+}
+
+impl CacheConfig {
+    pub fn new(capacity: usize) -> Self {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let minutes = Duration::from_secs(10 * 60);
+
+        let mut ttls = HashMap::new();
+        ttls.insert(GeoNamesApi::CountryInfo, 7 * day);
+        ttls.insert(GeoNamesApi::PostalCodeCountryInfo, 7 * day);
+        ttls.insert(GeoNamesApi::Neighbours, 7 * day);
+        ttls.insert(GeoNamesApi::Weather, minutes);
+        ttls.insert(GeoNamesApi::WeatherIcao, minutes);
+        ttls.insert(GeoNamesApi::FindNearByWeather, minutes);
+        ttls.insert(GeoNamesApi::Earthquakes, minutes);
+
+        CacheConfig {
+            capacity,
+            default_ttl: Duration::from_secs(60 * 60),
+            ttls,
+        }
+    }
+
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    // A zero TTL disables caching for the endpoint
+    pub fn ttl(mut self, api: GeoNamesApi, ttl: Duration) -> Self {
+        self.ttls.insert(api, ttl);
+        self
+    }
+
+    pub fn ttl_for(&self, api: GeoNamesApi) -> Duration {
+        self.ttls.get(&api).copied().unwrap_or(self.default_ttl)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    body: Bytes,
+    // None for a TTL too large to represent, which never expires
+    expires_at: Option<Instant>,
+    last_used: u64,
+}
+
+impl Entry {
+    fn is_fresh(&self, now: Instant) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, Entry>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    config: CacheConfig,
+    state: Mutex<CacheState>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        ResponseCache {
+            config,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    pub(crate) fn get(&self, api: GeoNamesApi, key: &str) -> Option<Bytes> {
+        if self.config.ttl_for(api).is_zero() {
+            return None;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+
+        let body = match state.entries.get_mut(key) {
+            Some(entry) if entry.is_fresh(Instant::now()) => {
+                entry.last_used = clock;
+                Some(entry.body.clone())
+            }
+            Some(_) => {
+                state.entries.remove(key);
+                None
+            }
+            None => None,
+        };
+
+        match body {
+            Some(_) => state.hits += 1,
+            None => state.misses += 1,
+        }
+        body
+    }
+
+    pub(crate) fn insert(&self, api: GeoNamesApi, key: String, body: Bytes) {
+        let ttl = self.config.ttl_for(api);
+        if ttl.is_zero() || self.config.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.config.capacity {
+            let now = Instant::now();
+            state.entries.retain(|_, entry| entry.is_fresh(now));
+        }
+
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.config.capacity {
+            let least_recently_used = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(lru) = least_recently_used {
+                state.entries.remove(&lru);
+            }
+        }
+
+        state.entries.insert(
+            key,
+            Entry {
+                body,
+                expires_at: Instant::now().checked_add(ttl),
+                last_used: clock,
+            },
+        );
+    }
+
+    pub(crate) fn remove(&self, key: &str) {
+        self.state.lock().unwrap().entries.remove(key);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.state.lock().unwrap().entries.clear();
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        let state = self.state.lock().unwrap();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.entries.len(),
+        }
+    }
+}
+
+// Params keep their keys sorted, so equal parameter sets always produce the same key. Keys and
+// values are encoded, so a value containing `&` or `=` cannot pass for another param
+pub(crate) fn cache_key(api: GeoNamesApi, params: Option<&Params>) -> String {
+    let api_name = serde_variant::to_variant_name(&api).unwrap_or_default();
+    let query = params
+        .map(|p| {
+            p.iter()
+                .map(|(param, value)| {
+                    format!("{}={}", form_urlencode(param), form_urlencode(value))
+                })
+                .collect::<Vec<_>>()
+                .join("&")
+        })
+        .unwrap_or_default();
+
+    format!("{}?{}", api_name, query)
+}
@@ -0,0 +1,153 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::redact::redact_url;
+use crate::{ApiError, RequestError, Transport, TransportResponse};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Interaction {
+    pub url: String,
+    pub status: u16,
+    pub body: String,
+}
+
+impl Cassette {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ApiError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ApiError::GeonamesApi(format!(
+                "Failed to read cassette '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        serde_json::from_str(&content).map_err(|e| {
+            ApiError::Deserialization(
+                RequestError::new(format!("Invalid cassette '{}': {}", path.display(), e))
+                    .with_source(e),
+            )
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ApiError> {
+        let path = path.as_ref();
+        let write_error = |e: std::io::Error| {
+            ApiError::GeonamesApi(format!(
+                "Failed to write cassette '{}': {}",
+                path.display(),
+                e
+            ))
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(write_error)?;
+        }
+
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            ApiError::Deserialization(
+                RequestError::new(format!("Failed to serialize cassette: {}", e)).with_source(e),
+            )
+        })?;
+        std::fs::write(path, content + "\n").map_err(write_error)
+    }
+
+    fn find(&self, url: &str) -> Option<&Interaction> {
+        self.interactions
+            .iter()
+            .find(|interaction| interaction.url == url)
+    }
+}
+
+enum Mode {
+    Record {
+        inner: Arc<dyn Transport>,
+        path: PathBuf,
+    },
+    Replay,
+}
+
+pub struct CassetteTransport {
+    mode: Mode,
+    cassette: Mutex<Cassette>,
+}
+
+impl CassetteTransport {
+    // Starts an empty cassette and saves it to `path` after every request sent through `inner`
+    pub fn record(path: impl Into<PathBuf>, inner: impl Transport + 'static) -> Self {
+        CassetteTransport::recording(path.into(), Arc::new(inner))
+    }
+
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self, ApiError> {
+        Ok(CassetteTransport::from_cassette(Cassette::load(path)?))
+    }
+
+    pub fn from_cassette(cassette: Cassette) -> Self {
+        CassetteTransport {
+            mode: Mode::Replay,
+            cassette: Mutex::new(cassette),
+        }
+    }
+
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+
+    pub(crate) fn recording(path: PathBuf, inner: Arc<dyn Transport>) -> Self {
+        CassetteTransport {
+            mode: Mode::Record { inner, path },
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for CassetteTransport {
+    async fn get(&self, url: Url) -> Result<TransportResponse, ApiError> {
+        let redacted = redact_url(&url);
+
+        match &self.mode {
+            Mode::Record { inner, path } => {
+                let response = inner.get(url).await?;
+
+                let mut cassette = self.cassette.lock().unwrap();
+                cassette.interactions.retain(|i| i.url != redacted);
+                cassette.interactions.push(Interaction {
+                    url: redacted,
+                    status: response.status,
+                    body: String::from_utf8_lossy(&response.body).into_owned(),
+                });
+                cassette.save(path)?;
+
+                Ok(response)
+            }
+            Mode::Replay => {
+                let cassette = self.cassette.lock().unwrap();
+                let interaction = cassette.find(&redacted).ok_or_else(|| {
+                    ApiError::GeonamesApi(format!("No recorded response for {}", redacted))
+                })?;
+
+                Ok(TransportResponse {
+                    status: interaction.status,
+                    body: Bytes::from(interaction.body.clone()),
+                })
+            }
+        }
+    }
+}
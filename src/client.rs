@@ -1,10 +1,15 @@
-use async_trait::async_trait;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use reqwest::{Client, Url};
+use bytes::Bytes;
+use reqwest::{Client, Proxy, Url};
+use tokio::sync::OnceCell;
 
 use crate::{
+    cache::{cache_key, ResponseCache},
     config::{
         GeoNamesApi, ASTERGDEM_PARAMS, BASE_URI, BASE_URI_COMMERCIAL, COUNTRY_CODE_PARAMS,
         COUNTRY_INFO_PARAMS, COUNTRY_SUBDIVISION_PARAMS, FIND_NEARBY_PARAMS,
@@ -12,119 +17,853 @@ use crate::{
         OCEAN_PARAMS, POSTAL_CODE_LOOKUP_PARAMS, POSTAL_CODE_SEARCH_PARAMS, SRTM1_PARAMS,
         SRTM3_PARAMS, TIMEZONE_PARAMS,
     },
-    ApiError, ADDRESS_PARAMS, CHILDREN_PARAMS, CITIES_PARAMS, CONTAINS_PARAMS, EARTHQUAKES_PARAMS,
-    FIND_NEARBY_BY_WEATHER_PARAMS, FIND_NEARBY_BY_WIKIPEDIA_PARAMS, FIND_NEARBY_POIS_OSM_PARAMS,
-    FIND_NEARBY_STREETS_0SM_PARAMS, GEO_CODE_ADDRESS_PARAMS, HIERARCHY_PARAMS, NEIGHBOURS_PARAMS,
-    SEARCH_PARAMS, SIBLINGS_PARAMS, STREET_NAME_LOOKUP_PARAMS, WEATHER_ICAO_PARAMS, WEATHER_PARAMS,
-    WIKIPEDIA_BOUNDING_BOX_PARAMS, WIKIPEDIA_SEARCH_PARAMS,
+    disk_cache::DiskCache,
+    hosts::{Host, HostPool},
+    rate_limit::CreditLimiter,
+    redact::{Redactor, REDACTED},
+    response::StatusEnvelope,
+    telemetry::Telemetry,
+    ApiError, CacheConfig, CacheStats, CassetteMode, CassetteTransport, CreditBudget, CreditLimit,
+    CreditUsage, DiskCacheConfig, Endpoint, GeoNamesRequest, HostStatus, MetricsRecorder,
+    Middleware, MiddlewareAction, ParamTable, Params, RawResponse, RequestContext, RequestError,
+    ReqwestTransport, RetryPolicy, Transport, TransportResponse, ADDRESS_PARAMS, CHILDREN_PARAMS,
+    CITIES_PARAMS, CONTAINS_PARAMS, EARTHQUAKES_PARAMS, FIND_NEARBY_BY_WEATHER_PARAMS,
+    FIND_NEARBY_BY_WIKIPEDIA_PARAMS, FIND_NEARBY_POIS_OSM_PARAMS, FIND_NEARBY_STREETS_0SM_PARAMS,
+    FIND_NEAREST_INTERSECTION_OSM_PARAMS, GEO_CODE_ADDRESS_PARAMS, HIERARCHY_PARAMS,
+    NEIGHBOURS_PARAMS, SEARCH_PARAMS, SIBLINGS_PARAMS, STREET_NAME_LOOKUP_PARAMS,
+    WEATHER_ICAO_PARAMS, WEATHER_PARAMS, WIKIPEDIA_BOUNDING_BOX_PARAMS, WIKIPEDIA_SEARCH_PARAMS,
 };
 
-#[async_trait]
 pub trait ApiEndpoint {
-    fn allowed_params(&self) -> Option<&'static HashMap<&'static str, Vec<&'static str>>>;
-    async fn call_api<T: DeserializeOwned + Serialize>(
-        &self,
-        params: Option<HashMap<&'static str, &'static str>>,
-    ) -> Result<T, ApiError>;
+    fn allowed_params(&self) -> Option<&'static ParamTable>;
 }
 
+pub(crate) const DEFAULT_USER_AGENT: &str = concat!("geonames-rs/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Clone)]
 pub struct ApiClient {
-    api: GeoNamesApi,
-    username: &'static str,
-    token: Option<&'static str>,
+    transport: Arc<dyn Transport>,
+    username: String,
+    token: Option<String>,
+    hosts: Arc<HostPool>,
+    redactor: Redactor,
+    telemetry: Arc<Telemetry>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    retry_policy: RetryPolicy,
+    credit_limiter: Option<Arc<CreditLimiter>>,
+    cache: Option<Arc<ResponseCache>>,
+    disk_cache: Option<Arc<DiskCache>>,
+    coalesce_requests: bool,
+    in_flight: Arc<Mutex<HashMap<String, InFlight>>>,
 }
 
-impl ApiClient {
-    pub fn new(api: GeoNamesApi, username: &'static str, token: Option<&'static str>) -> Self {
-        ApiClient {
-            api,
-            username,
-            token,
+type InFlight = Arc<OnceCell<Result<Fetched, Failure>>>;
+
+const DEFAULT_HOST_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse<T> {
+    pub data: T,
+    // None when the response was served from a cache
+    pub host: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Fetched {
+    body: Bytes,
+    host: Option<String>,
+    // None when the response came from a cache or a middleware
+    status: Option<u16>,
+    // Set for 2xx responses from the network, which are cached once they deserialize
+    cacheable: bool,
+}
+
+// A failed fetch with the response of the host that caused it, for the response hooks
+#[derive(Debug, Clone)]
+struct Failure {
+    error: ApiError,
+    response: Option<RawResponse>,
+}
+
+impl From<ApiError> for Failure {
+    fn from(error: ApiError) -> Self {
+        Failure {
+            error,
+            response: None,
         }
     }
 }
 
-#[async_trait]
-impl ApiEndpoint for ApiClient {
-    async fn call_api<T: DeserializeOwned + Serialize>(
+impl ApiClient {
+    pub fn new(username: impl Into<String>, token: Option<&str>) -> Self {
+        let mut builder = ApiClientBuilder::new(username);
+        if let Some(token) = token {
+            builder = builder.token(token);
+        }
+
+        builder
+            .build()
+            .expect("the default client configuration is valid")
+    }
+
+    pub fn builder(username: impl Into<String>) -> ApiClientBuilder {
+        ApiClientBuilder::new(username)
+    }
+
+    pub async fn call<T: DeserializeOwned>(
         &self,
-        params: Option<HashMap<&'static str, &'static str>>,
+        api: GeoNamesApi,
+        params: Option<Params>,
     ) -> Result<T, ApiError> {
-        let maybe_allowed_params = self.allowed_params();
-
-        match maybe_allowed_params {
-            Some(allowed_params) => {
-                if let Some(p) = params.clone() {
-                    for (param, value) in p.clone() {
-                        if !allowed_params.contains_key(param) {
-                            return Err(ApiError::InvalidParams(format!(
-                                "Param '{}' not allowed for this API",
-                                param
-                            )));
-                        }
-
-                        let allowed_values = allowed_params[param].clone();
-                        if !allowed_values.is_empty() && !allowed_values.contains(&value) {
-                            return Err(ApiError::InvalidParams(format!(
-                                "Invalid value '{}' for param '{}'",
-                                value, param
-                            )));
-                        }
+        Ok(self.call_detailed(api, params).await?.data)
+    }
+
+    // Like `call`, but also reports the host that served the response
+    pub async fn call_detailed<T: DeserializeOwned>(
+        &self,
+        api: GeoNamesApi,
+        params: Option<Params>,
+    ) -> Result<ApiResponse<T>, ApiError> {
+        #[cfg(feature = "tracing")]
+        let span = self.telemetry.span(api, params.as_ref());
+
+        let call = async move {
+            let started = Instant::now();
+            let result = self
+                .execute(api, params)
+                .await
+                .map_err(|e| e.redact(&self.redactor));
+            self.telemetry
+                .call_finished(api, started.elapsed(), result.as_ref().err());
+            result
+        };
+
+        #[cfg(feature = "tracing")]
+        let call = tracing::Instrument::instrument(call, span);
+
+        call.await
+    }
+
+    pub async fn call_endpoint<E: Endpoint>(
+        &self,
+        params: Option<Params>,
+    ) -> Result<E::Response, ApiError> {
+        self.call(E::API, params).await
+    }
+
+    pub async fn send<R: GeoNamesRequest>(
+        &self,
+        request: R,
+    ) -> Result<<R::Endpoint as Endpoint>::Response, ApiError> {
+        self.call_endpoint::<R::Endpoint>(request.into_params())
+            .await
+    }
+
+    // None when the client was built without a credit budget
+    pub fn credit_usage(&self) -> Option<CreditUsage> {
+        self.credit_limiter.as_ref().map(|limiter| limiter.usage())
+    }
+
+    pub fn remaining_credits(&self) -> Vec<(CreditLimit, u64)> {
+        self.credit_limiter
+            .as_ref()
+            .map(|limiter| limiter.remaining())
+            .unwrap_or_default()
+    }
+
+    pub fn host_status(&self) -> Vec<HostStatus> {
+        self.hosts.statuses()
+    }
+
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+        if let Some(disk_cache) = &self.disk_cache {
+            disk_cache.clear();
+        }
+    }
+
+    pub fn invalidate(&self, api: GeoNamesApi, params: Option<&Params>) {
+        let key = cache_key(api, params);
+        if let Some(cache) = &self.cache {
+            cache.remove(&key);
+        }
+        if let Some(disk_cache) = &self.disk_cache {
+            disk_cache.remove(&key);
+        }
+    }
+
+    async fn execute<T: DeserializeOwned>(
+        &self,
+        api: GeoNamesApi,
+        params: Option<Params>,
+    ) -> Result<ApiResponse<T>, ApiError> {
+        validate_params(api, params.as_ref())?;
+
+        // Params added by a middleware are not validated, so they can carry e.g. tenant tags
+        let mut params = params.unwrap_or_default();
+        let mut layers = 0;
+        let mut canned = None;
+        for middleware in &self.middleware {
+            match middleware.on_request(api, &mut params).await? {
+                MiddlewareAction::Continue => layers += 1,
+                MiddlewareAction::Respond(body) => {
+                    canned = Some(body);
+                    break;
+                }
+            }
+        }
+
+        let request_params = (!params.is_empty()).then_some(&params);
+        let fetched = match canned {
+            Some(body) => Fetched {
+                body,
+                host: None,
+                status: None,
+                cacheable: false,
+            },
+            None => match self.fetch_cached(api, request_params).await {
+                Ok(fetched) => fetched,
+                Err(Failure { error, response }) => {
+                    if let Some(response) = response {
+                        self.on_response(layers, api, &params, &response).await?;
                     }
+                    return Err(error);
                 }
+            },
+        };
+
+        let response = RawResponse {
+            body: fetched.body,
+            host: fetched.host,
+            status: fetched.status,
+        };
+        self.on_response(layers, api, &params, &response).await?;
+
+        let host = response.host.as_deref().unwrap_or(self.hosts.primary());
+        let data = deserialize(&response.body).map_err(|e| {
+            let context = request_context(
+                host,
+                api,
+                request_params,
+                &self.username,
+                self.token.as_deref(),
+            );
+            match fetched.status {
+                Some(status) => e.with_context(context.with_http_status(status)),
+                None => e.with_context(context),
             }
-            None => {
-                if params.is_some() {
-                    return Err(ApiError::InvalidParams(format!(
-                        "Params for api {:?} should be None",
-                        self.api
-                    )));
+        })?;
+
+        if fetched.cacheable {
+            self.store(api, cache_key(api, request_params), &response.body)
+                .await;
+        }
+
+        Ok(ApiResponse {
+            data,
+            host: response.host,
+        })
+    }
+
+    // Runs the response hooks of the layers that passed the request, in reverse
+    async fn on_response(
+        &self,
+        layers: usize,
+        api: GeoNamesApi,
+        params: &Params,
+        response: &RawResponse,
+    ) -> Result<(), ApiError> {
+        for middleware in self.middleware[..layers].iter().rev() {
+            middleware.on_response(api, params, response).await?;
+        }
+        Ok(())
+    }
+
+    async fn fetch_cached(
+        &self,
+        api: GeoNamesApi,
+        params: Option<&Params>,
+    ) -> Result<Fetched, Failure> {
+        // Cache hits are served before the limiter, so they never cost credits
+        let key = cache_key(api, params);
+        let cached = self.cached(api, &key).await;
+        if self.cache.is_some() || self.disk_cache.is_some() {
+            self.telemetry.cache_lookup(api, cached.is_some());
+        }
+
+        match cached {
+            Some(body) => Ok(Fetched {
+                body,
+                host: None,
+                status: None,
+                cacheable: false,
+            }),
+            None if self.coalesce_requests => self.fetch_coalesced(api, params, &key).await,
+            None => self.fetch_with_retry(api, params).await,
+        }
+    }
+
+    // Disk hits are copied into the in-memory cache
+    async fn cached(&self, api: GeoNamesApi, key: &str) -> Option<Bytes> {
+        if let Some(body) = self.cache.as_ref().and_then(|cache| cache.get(api, key)) {
+            return Some(body);
+        }
+
+        let disk_cache = self.disk_cache.clone()?;
+        let disk_key = key.to_string();
+        let body = tokio::task::spawn_blocking(move || disk_cache.get(api, &disk_key))
+            .await
+            .ok()??;
+        if let Some(cache) = &self.cache {
+            cache.insert(api, key.to_string(), body.clone());
+        }
+        Some(body)
+    }
+
+    // Concurrent calls for the same key share the first caller's request and its result, only the
+    // first caller stores the response
+    async fn fetch_coalesced(
+        &self,
+        api: GeoNamesApi,
+        params: Option<&Params>,
+        key: &str,
+    ) -> Result<Fetched, Failure> {
+        let cell = self
+            .in_flight
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+
+        let mut first = false;
+        let result = cell
+            .get_or_init(|| {
+                first = true;
+                self.fetch_with_retry(api, params)
+            })
+            .await
+            .clone();
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.get(key).is_some_and(|c| Arc::ptr_eq(c, &cell)) {
+            in_flight.remove(key);
+        }
+
+        result.map(|fetched| Fetched {
+            cacheable: fetched.cacheable && first,
+            ..fetched
+        })
+    }
+
+    async fn store(&self, api: GeoNamesApi, key: String, body: &Bytes) {
+        if let Some(disk_cache) = self.disk_cache.clone() {
+            let (key, body) = (key.clone(), body.clone());
+            let _ = tokio::task::spawn_blocking(move || disk_cache.insert(api, &key, &body)).await;
+        }
+        if let Some(cache) = &self.cache {
+            cache.insert(api, key, body.clone());
+        }
+    }
+
+    async fn fetch_with_retry(
+        &self,
+        api: GeoNamesApi,
+        params: Option<&Params>,
+    ) -> Result<Fetched, Failure> {
+        let mut attempt = 1;
+        let mut credits = 0;
+        loop {
+            let result = self.fetch(api, params, &mut credits).await;
+            if let (Some(limiter), Err(failure)) = (&self.credit_limiter, &result) {
+                limiter.record_limit_error(&failure.error);
+            }
+
+            match result {
+                Err(failure) if self.retry_policy.should_retry(&failure.error, attempt) => {
+                    self.telemetry.retry(api, attempt, &failure.error);
+                    tokio::time::sleep(self.retry_policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => {
+                    self.telemetry.fetch_finished(attempt, credits);
+                    return result;
                 }
             }
-        };
+        }
+    }
+
+    async fn fetch(
+        &self,
+        api: GeoNamesApi,
+        params: Option<&Params>,
+        credits: &mut u64,
+    ) -> Result<Fetched, Failure> {
+        let mut last_error: Option<Failure> = None;
+
+        for host in self.hosts.ordered() {
+            let token = self.token.as_deref().filter(|_| host.send_token);
+            let url = request_url(&host.url, api, params, &self.username, token)?;
+            let context = RequestContext::new(api, &url);
+
+            // Every host request takes credits, which are given back when GeoNames did not bill it
+            let reserved = match &self.credit_limiter {
+                Some(limiter) => limiter.acquire(api, params).await?,
+                None => 0,
+            };
+
+            let started = Instant::now();
+            let result = self.transport.get(url).await;
+            let billed = self.telemetry.http_response(
+                api,
+                params,
+                &host.url,
+                result.as_ref().ok().map(|response| response.status),
+                result.as_ref().map_or(0, |response| response.body.len()),
+                started.elapsed(),
+            );
+            *credits += billed;
+            if let (Some(limiter), true) = (&self.credit_limiter, billed < reserved) {
+                limiter.refund(api, reserved - billed);
+            }
 
-        let api_name = serde_variant::to_variant_name(&self.api).unwrap(); // TODO: Return err
+            // Unreachable hosts, server errors and overloaded or timed out databases put the host
+            // in cooldown and move on to the next
+            let response = match result {
+                Ok(response) => response,
+                Err(error) => {
+                    self.hosts.record_failure(&host.url);
+                    last_error = Some(error.with_context(context).into());
+                    continue;
+                }
+            };
+
+            let status = response.status;
+            let raw = RawResponse {
+                body: response.body.clone(),
+                host: Some(host.url.clone()),
+                status: Some(status),
+            };
+            let result = if status >= 500 {
+                Err(http_status_error(response, context))
+            } else {
+                read_response(response, context)
+            };
+
+            match result {
+                Err(
+                    error @ (ApiError::HttpStatus(_)
+                    | ApiError::ServerOverloaded(_)
+                    | ApiError::DatabaseTimeout(_)),
+                ) => {
+                    self.hosts.record_failure(&host.url);
+                    last_error = Some(Failure {
+                        error,
+                        response: Some(raw),
+                    });
+                }
+                Err(error) => {
+                    self.hosts.record_success(&host.url);
+                    return Err(Failure {
+                        error,
+                        response: Some(raw),
+                    });
+                }
+                Ok(body) => {
+                    self.hosts.record_success(&host.url);
+                    return Ok(Fetched {
+                        body,
+                        host: raw.host,
+                        status: Some(status),
+                        cacheable: (200..300).contains(&status),
+                    });
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| no_host_error().into()))
+    }
+}
+
+impl fmt::Debug for ApiClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiClient")
+            .field("username", &REDACTED)
+            .field("token", &self.token.as_ref().map(|_| REDACTED))
+            .field("hosts", &self.hosts.statuses())
+            .field("retry_policy", &self.retry_policy)
+            .field("cache", &self.cache.is_some())
+            .field("disk_cache", &self.disk_cache.is_some())
+            .field("credit_budget", &self.credit_limiter.is_some())
+            .finish()
+    }
+}
+
+pub struct ApiClientBuilder {
+    username: String,
+    token: Option<String>,
+    base_url: Option<String>,
+    fallback_hosts: Vec<String>,
+    transport: Option<Arc<dyn Transport>>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: String,
+    gzip: bool,
+    retry_policy: RetryPolicy,
+    credit_budget: Option<CreditBudget>,
+    cassette: Option<CassetteMode>,
+    cache: Option<CacheConfig>,
+    disk_cache: Option<DiskCacheConfig>,
+    coalesce_requests: bool,
+    free_server_fallback: Option<String>,
+    host_cooldown: Duration,
+    metrics_recorder: Option<Arc<dyn MetricsRecorder>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl ApiClientBuilder {
+    pub fn new(username: impl Into<String>) -> Self {
+        ApiClientBuilder {
+            username: username.into(),
+            token: None,
+            base_url: None,
+            fallback_hosts: vec![],
+            transport: None,
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            gzip: true,
+            retry_policy: RetryPolicy::default(),
+            credit_budget: None,
+            cassette: None,
+            cache: None,
+            disk_cache: None,
+            coalesce_requests: true,
+            free_server_fallback: None,
+            host_cooldown: DEFAULT_HOST_COOLDOWN,
+            metrics_recorder: None,
+            middleware: vec![],
+        }
+    }
+
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    pub fn fallback_host(mut self, host: impl Into<String>) -> Self {
+        self.fallback_hosts.push(host.into());
+        self
+    }
+
+    pub fn fallback_hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fallback_hosts
+            .extend(hosts.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn http_client(mut self, http_client: Client) -> Self {
+        self.transport = Some(Arc::new(ReqwestTransport::new(http_client)));
+        self
+    }
+
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn credit_budget(mut self, credit_budget: CreditBudget) -> Self {
+        self.credit_budget = Some(credit_budget);
+        self
+    }
+
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn disk_cache(mut self, disk_cache: DiskCacheConfig) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    pub fn coalesce_requests(mut self, coalesce_requests: bool) -> Self {
+        self.coalesce_requests = coalesce_requests;
+        self
+    }
+
+    // Last resort after every configured host failed, requests to it are sent without the token
+    pub fn free_server_fallback(mut self, enabled: bool) -> Self {
+        self.free_server_fallback = enabled.then(|| BASE_URI.to_string());
+        self
+    }
+
+    pub fn free_server_host(mut self, host: impl Into<String>) -> Self {
+        self.free_server_fallback = Some(host.into());
+        self
+    }
+
+    // How long a host that failed is tried after the healthy ones
+    pub fn host_cooldown(mut self, cooldown: Duration) -> Self {
+        self.host_cooldown = cooldown;
+        self
+    }
+
+    pub fn metrics_recorder(mut self, recorder: impl MetricsRecorder + 'static) -> Self {
+        self.metrics_recorder = Some(Arc::new(recorder));
+        self
+    }
+
+    // Layers run in the order they are added
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
 
-        let base_url = match self.token {
-            Some(_) => format!("{}{}{}", BASE_URI_COMMERCIAL, api_name, "JSON"),
-            None => format!("{}{}{}", BASE_URI, api_name, "JSON"),
+    // Record wraps the configured transport, replay serves every request from the cassette
+    pub fn cassette(mut self, cassette: CassetteMode) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    pub fn build(self) -> Result<ApiClient, ApiError> {
+        let base_url = match self.base_url {
+            Some(base_url) => normalize_base_url(base_url)?,
+            None => default_base_url(self.token.as_deref()),
         };
 
-        let mut url = Url::parse(&base_url)
-            .map_err(|e| ApiError::UrlParse(format!("Failed to parse URL: {}", e)))?;
+        let mut hosts = std::iter::once(Ok(base_url))
+            .chain(self.fallback_hosts.into_iter().map(normalize_base_url))
+            .map(|url| {
+                url.map(|url| Host {
+                    url,
+                    send_token: true,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        if let Some(p) = params {
-            for (param, value) in p {
-                url.query_pairs_mut().append_pair(param, value);
+        if let Some(free_host) = self.free_server_fallback {
+            let url = normalize_base_url(free_host)?;
+            if !hosts.iter().any(|host| host.url == url) {
+                hosts.push(Host {
+                    url,
+                    send_token: false,
+                });
             }
         }
 
-        if let Some(token) = self.token {
-            url.query_pairs_mut().append_pair("token", token);
+        // An injected client or transport is used as is, HTTP settings only apply to the default one
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => {
+                let mut builder = Client::builder()
+                    .user_agent(self.user_agent)
+                    .gzip(self.gzip);
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+
+                let http = builder.build().map_err(|e| {
+                    ApiError::GeonamesApi(format!("Failed to build HTTP client: {}", e))
+                })?;
+                Arc::new(ReqwestTransport::new(http))
+            }
+        };
+
+        let transport: Arc<dyn Transport> = match self.cassette {
+            Some(CassetteMode::Record(path)) => {
+                Arc::new(CassetteTransport::recording(path, transport))
+            }
+            Some(CassetteMode::Replay(path)) => Arc::new(CassetteTransport::replay(path)?),
+            None => transport,
+        };
+
+        let redactor = Redactor::new(&self.username, self.token.as_deref());
+        let telemetry = Telemetry::new(
+            self.metrics_recorder,
+            self.credit_budget.clone().unwrap_or_default(),
+            redactor.clone(),
+        );
+
+        Ok(ApiClient {
+            transport,
+            username: self.username,
+            token: self.token,
+            hosts: Arc::new(HostPool::new(hosts, self.host_cooldown)),
+            redactor,
+            telemetry: Arc::new(telemetry),
+            middleware: self.middleware,
+            retry_policy: self.retry_policy,
+            credit_limiter: self
+                .credit_budget
+                .map(|budget| Arc::new(CreditLimiter::new(budget))),
+            cache: self
+                .cache
+                .map(|config| Arc::new(ResponseCache::new(config))),
+            disk_cache: self
+                .disk_cache
+                .map(|config| Arc::new(DiskCache::new(config))),
+            coalesce_requests: self.coalesce_requests,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+}
+
+pub(crate) fn validate_params(api: GeoNamesApi, params: Option<&Params>) -> Result<(), ApiError> {
+    match api.allowed_params() {
+        Some(allowed_params) => match params {
+            Some(p) => allowed_params.validate(p),
+            None => allowed_params.validate(&Params::new()),
+        },
+        None if params.is_some() => Err(ApiError::InvalidParams(format!(
+            "Params for api {:?} should be None",
+            api
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn request_url(
+    host: &str,
+    api: GeoNamesApi,
+    params: Option<&Params>,
+    username: &str,
+    token: Option<&str>,
+) -> Result<Url, ApiError> {
+    let api_name = serde_variant::to_variant_name(&api).unwrap(); // TODO: Return err
+    let mut url = Url::parse(&format!("{}{}{}", host, api_name, "JSON"))
+        .map_err(|e| ApiError::UrlParse(format!("Failed to parse URL: {}", e)))?;
+
+    if let Some(p) = params {
+        for (param, value) in p.iter() {
+            url.query_pairs_mut().append_pair(param, value);
         }
+    }
 
-        url.query_pairs_mut().append_pair("username", self.username);
+    if let Some(token) = token {
+        url.query_pairs_mut().append_pair("token", token);
+    }
 
-        let client = Client::new();
-        let response = client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| ApiError::GeonamesApi(format!("Geonames api error: {}", e)))?;
+    url.query_pairs_mut().append_pair("username", username);
 
-        let res = response
-            .bytes()
-            .await
-            .map_err(|e| ApiError::Deserialization(format!("Deserialization error: {}", e)))?;
+    Ok(url)
+}
 
-        let api_res = serde_json::from_slice(&res)
-            .map_err(|e| ApiError::Deserialization(format!("Deserialization error: {}", e)))?;
+// Context for errors raised after the response was received, such as deserialization errors
+pub(crate) fn request_context(
+    host: &str,
+    api: GeoNamesApi,
+    params: Option<&Params>,
+    username: &str,
+    token: Option<&str>,
+) -> RequestContext {
+    match request_url(host, api, params, username, token) {
+        Ok(url) => RequestContext::new(api, &url),
+        Err(_) => RequestContext {
+            endpoint: api,
+            url: host.to_string(),
+            http_status: None,
+        },
+    }
+}
 
-        Ok(api_res)
+pub(crate) fn deserialize<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(|e| {
+        ApiError::Deserialization(
+            RequestError::new(format!("Deserialization error: {}", e))
+                .with_body(body)
+                .with_source(e),
+        )
+    })
+}
+
+pub(crate) fn http_status_error(response: TransportResponse, context: RequestContext) -> ApiError {
+    ApiError::HttpStatus(
+        RequestError::new(format!("Server error {}", response.status))
+            .with_body(&response.body)
+            .with_context(context.with_http_status(response.status)),
+    )
+}
+
+pub(crate) fn no_host_error() -> ApiError {
+    ApiError::GeonamesApi("No host configured".to_string())
+}
+
+pub(crate) fn default_base_url(token: Option<&str>) -> String {
+    match token {
+        Some(_) => BASE_URI_COMMERCIAL.to_string(),
+        None => BASE_URI.to_string(),
     }
+}
+
+pub(crate) fn normalize_base_url(mut base_url: String) -> Result<String, ApiError> {
+    if !base_url.ends_with('/') {
+        base_url.push('/');
+    }
+
+    Url::parse(&base_url)
+        .map_err(|e| ApiError::UrlParse(format!("Invalid base URL '{}': {}", base_url, e)))?;
 
-    fn allowed_params(&self) -> Option<&'static HashMap<&'static str, Vec<&'static str>>> {
-        match self.api {
+    Ok(base_url)
+}
+
+impl ApiEndpoint for GeoNamesApi {
+    fn allowed_params(&self) -> Option<&'static ParamTable> {
+        match self {
             GeoNamesApi::Astergdem => Some(&ASTERGDEM_PARAMS),
             GeoNamesApi::Children => Some(&CHILDREN_PARAMS),
             GeoNamesApi::Cities => Some(&CITIES_PARAMS),
@@ -139,7 +878,7 @@ impl ApiEndpoint for ApiClient {
             GeoNamesApi::FindNearbyStreetsOsm => Some(&FIND_NEARBY_STREETS_0SM_PARAMS),
             GeoNamesApi::FindNearByWeather => Some(&FIND_NEARBY_BY_WEATHER_PARAMS),
             GeoNamesApi::FindNearbyWikipedia => Some(&FIND_NEARBY_BY_WIKIPEDIA_PARAMS),
-            GeoNamesApi::FindNearestIntersectionOsm => None,
+            GeoNamesApi::FindNearestIntersectionOsm => Some(&FIND_NEAREST_INTERSECTION_OSM_PARAMS),
             GeoNamesApi::FindNearbyPoisOsm => Some(&FIND_NEARBY_POIS_OSM_PARAMS),
             GeoNamesApi::Address => Some(&ADDRESS_PARAMS),
             GeoNamesApi::GeoCodeAddress => Some(&GEO_CODE_ADDRESS_PARAMS),
@@ -164,3 +903,15 @@ impl ApiEndpoint for ApiClient {
         }
     }
 }
+
+pub(crate) fn read_response(
+    response: TransportResponse,
+    context: RequestContext,
+) -> Result<Bytes, ApiError> {
+    if let Ok(envelope) = serde_json::from_slice::<StatusEnvelope>(&response.body) {
+        let error = ApiError::from(envelope.status);
+        return Err(error.with_context(context.with_http_status(response.status)));
+    }
+
+    Ok(response.body)
+}
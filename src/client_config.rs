@@ -0,0 +1,303 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::redact::REDACTED;
+use crate::{
+    ApiClient, ApiClientBuilder, ApiError, CacheConfig, CreditBudget, DiskCacheConfig,
+    LimitBehavior, RetryPolicy,
+};
+
+// Everything needed to build a client, loaded from the environment or a config file
+#[derive(Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientConfig {
+    pub username: String,
+    pub token: Option<String>,
+    pub base_url: Option<String>,
+    pub fallback_hosts: Vec<String>,
+    pub free_server_fallback: bool,
+    pub timeout_secs: Option<u64>,
+    pub connect_timeout_secs: Option<u64>,
+    pub user_agent: Option<String>,
+    pub retry: Option<RetrySettings>,
+    pub cache: Option<CacheSettings>,
+    pub disk_cache: Option<DiskCacheSettings>,
+    pub credits: Option<CreditSettings>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetrySettings {
+    pub max_attempts: Option<u32>,
+    pub initial_backoff_ms: Option<u64>,
+    pub max_backoff_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheSettings {
+    pub capacity: usize,
+    pub ttl_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DiskCacheSettings {
+    pub dir: PathBuf,
+    pub ttl_secs: Option<u64>,
+    pub max_bytes: Option<u64>,
+}
+
+// Without hourly or daily limits the budget of a free account is used
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CreditSettings {
+    pub hourly: Option<u64>,
+    pub daily: Option<u64>,
+    pub fail_fast: bool,
+}
+
+impl ClientConfig {
+    pub fn new(username: impl Into<String>) -> Self {
+        ClientConfig {
+            username: username.into(),
+            ..ClientConfig::default()
+        }
+    }
+
+    pub fn from_env() -> Result<Self, ApiError> {
+        ClientConfig::default().with_env()
+    }
+
+    // Overrides the config with the GEONAMES_* variables that are set, e.g. a token kept out of a file
+    pub fn with_env(self) -> Result<Self, ApiError> {
+        self.apply_vars(env_var)
+    }
+
+    // Like `with_env`, with the GEONAMES_* variables looked up in `vars` instead of the environment
+    pub fn with_vars(self, vars: impl Fn(&str) -> Option<String>) -> Result<Self, ApiError> {
+        self.apply_vars(|name| Ok(vars(name)))
+    }
+
+    fn apply_vars(
+        mut self,
+        lookup: impl Fn(&str) -> Result<Option<String>, ApiError>,
+    ) -> Result<Self, ApiError> {
+        // Empty variables count as unset
+        let var = |name: &str| Ok::<_, ApiError>(lookup(name)?.filter(|value| !value.is_empty()));
+
+        if let Some(username) = var("GEONAMES_USERNAME")? {
+            self.username = username;
+        }
+        if let Some(token) = var("GEONAMES_TOKEN")? {
+            self.token = Some(token);
+        }
+        if let Some(base_url) = var("GEONAMES_BASE_URL")? {
+            self.base_url = Some(base_url);
+        }
+        if let Some(hosts) = var("GEONAMES_FALLBACK_HOSTS")? {
+            self.fallback_hosts = hosts
+                .split(',')
+                .map(str::trim)
+                .filter(|host| !host.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        if let Some(secs) = var("GEONAMES_TIMEOUT_SECS")? {
+            self.timeout_secs = Some(parse_env("GEONAMES_TIMEOUT_SECS", &secs)?);
+        }
+        if let Some(secs) = var("GEONAMES_CONNECT_TIMEOUT_SECS")? {
+            self.connect_timeout_secs = Some(parse_env("GEONAMES_CONNECT_TIMEOUT_SECS", &secs)?);
+        }
+
+        Ok(self)
+    }
+
+    // The format is picked by the file extension, `.json` or `.toml`
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ApiError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            ApiError::Config(format!(
+                "Failed to read config file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => ClientConfig::from_json_str(&contents),
+            Some("toml") => ClientConfig::from_toml_str(&contents),
+            _ => Err(ApiError::Config(format!(
+                "Unsupported config file {}, expected a .json or .toml file",
+                path.display()
+            ))),
+        }
+    }
+
+    pub fn from_json_str(contents: &str) -> Result<Self, ApiError> {
+        serde_json::from_str(contents)
+            .map_err(|e| ApiError::Config(format!("Invalid JSON config: {}", e)))
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(contents: &str) -> Result<Self, ApiError> {
+        toml::from_str(contents)
+            .map_err(|e| ApiError::Config(format!("Invalid TOML config: {}", e)))
+    }
+
+    #[cfg(not(feature = "toml"))]
+    pub fn from_toml_str(_contents: &str) -> Result<Self, ApiError> {
+        Err(ApiError::Config(
+            "TOML config files need the `toml` feature".to_string(),
+        ))
+    }
+
+    // A builder with the config applied, for settings that only exist in code such as a transport
+    pub fn builder(self) -> Result<ApiClientBuilder, ApiError> {
+        if self.username.is_empty() {
+            return Err(ApiError::Config(
+                "A username is required, set it in the config or GEONAMES_USERNAME".to_string(),
+            ));
+        }
+
+        let mut builder = ApiClient::builder(self.username)
+            .fallback_hosts(self.fallback_hosts)
+            .free_server_fallback(self.free_server_fallback);
+
+        if let Some(token) = self.token {
+            builder = builder.token(token);
+        }
+        if let Some(base_url) = self.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(secs) = self.timeout_secs {
+            builder = builder.timeout(Duration::from_secs(secs));
+        }
+        if let Some(secs) = self.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(user_agent) = self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(retry) = self.retry {
+            builder = builder.retry_policy(retry.policy());
+        }
+        if let Some(cache) = self.cache {
+            builder = builder.cache(cache.config());
+        }
+        if let Some(disk_cache) = self.disk_cache {
+            builder = builder.disk_cache(disk_cache.config());
+        }
+        if let Some(credits) = self.credits {
+            builder = builder.credit_budget(credits.budget());
+        }
+
+        Ok(builder)
+    }
+
+    pub fn build(self) -> Result<ApiClient, ApiError> {
+        self.builder()?.build()
+    }
+}
+
+impl fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field("username", &REDACTED)
+            .field("token", &self.token.as_ref().map(|_| REDACTED))
+            .field("base_url", &self.base_url)
+            .field("fallback_hosts", &self.fallback_hosts)
+            .field("free_server_fallback", &self.free_server_fallback)
+            .field("timeout_secs", &self.timeout_secs)
+            .field("connect_timeout_secs", &self.connect_timeout_secs)
+            .field("user_agent", &self.user_agent)
+            .field("retry", &self.retry)
+            .field("cache", &self.cache)
+            .field("disk_cache", &self.disk_cache)
+            .field("credits", &self.credits)
+            .finish()
+    }
+}
+
+impl RetrySettings {
+    pub fn policy(&self) -> RetryPolicy {
+        let mut policy = RetryPolicy::default();
+        if let Some(max_attempts) = self.max_attempts {
+            policy = policy.max_attempts(max_attempts);
+        }
+
+        let initial_backoff = self
+            .initial_backoff_ms
+            .map_or(policy.initial_backoff, Duration::from_millis);
+        let max_backoff = self
+            .max_backoff_ms
+            .map_or(policy.max_backoff, Duration::from_millis);
+        policy.backoff(initial_backoff, max_backoff)
+    }
+}
+
+impl CacheSettings {
+    pub fn config(&self) -> CacheConfig {
+        let config = CacheConfig::new(self.capacity);
+        match self.ttl_secs {
+            Some(secs) => config.default_ttl(Duration::from_secs(secs)),
+            None => config,
+        }
+    }
+}
+
+impl DiskCacheSettings {
+    pub fn config(&self) -> DiskCacheConfig {
+        let mut config = DiskCacheConfig::new(&self.dir);
+        if let Some(secs) = self.ttl_secs {
+            config = config.default_ttl(Duration::from_secs(secs));
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            config = config.max_bytes(max_bytes);
+        }
+        config
+    }
+}
+
+impl CreditSettings {
+    pub fn budget(&self) -> CreditBudget {
+        let mut budget = match (self.hourly, self.daily) {
+            (None, None) => CreditBudget::default(),
+            (hourly, daily) => {
+                let mut budget = CreditBudget::new();
+                if let Some(credits) = hourly {
+                    budget = budget.hourly(credits);
+                }
+                if let Some(credits) = daily {
+                    budget = budget.daily(credits);
+                }
+                budget
+            }
+        };
+
+        if self.fail_fast {
+            budget = budget.behavior(LimitBehavior::FailFast);
+        }
+        budget
+    }
+}
+
+fn env_var(name: &str) -> Result<Option<String>, ApiError> {
+    match std::env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(ApiError::Config(format!("Invalid {}: {}", name, e))),
+    }
+}
+
+fn parse_env(name: &str, value: &str) -> Result<u64, ApiError> {
+    value.trim().parse().map_err(|e| {
+        ApiError::Config(format!(
+            "Invalid {} '{}': {}, expected a number of seconds",
+            name, value, e
+        ))
+    })
+}
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
+use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+use crate::{ParamKind, ParamSpec, ParamTable};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub enum GeoNamesApi {
     Astergdem,
@@ -51,316 +54,384 @@ pub enum Format {
     Json,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Style {
+    Short,
+    Medium,
+    Long,
+    Full,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Operator {
+    And,
+    Or,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Cities {
+    Cities1000,
+    Cities5000,
+    Cities15000,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Hierarchy {
+    Tourism,
+    Geography,
+    Dependency,
+}
+
+macro_rules! display_variant_name {
+    ($($ty:ty),*) => {
+        $(
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(serde_variant::to_variant_name(self).map_err(|_| fmt::Error)?)
+                }
+            }
+        )*
+    };
+}
+
+display_variant_name!(Style, Operator, Cities, Hierarchy);
+
 lazy_static::lazy_static! {
-    pub static ref POSTAL_CODE_SEARCH_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("postalcode", vec![]);
-        map.insert("postalcode_startsWith", vec![]);
-        map.insert("placename", vec![]);
-        map.insert("placename_startsWith", vec![]);
-        map.insert("country", vec![]);
-        map.insert("countryBias", vec![]);
-        map.insert("maxRows", vec![]);
-        map.insert("style", vec!["SHORT", "MEDIUM", "LONG", "FULL"]);
-        map.insert("operator", vec!["AND", "OR"]);
-        map.insert("charset", vec![]);
-        map.insert("isReduced", vec!["true", "false"]);
-        map
-    };
-    pub static ref POSTAL_CODE_LOOKUP_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("postalcode", vec![]);
-        map.insert("country", vec![]);
-        map.insert("maxRows", vec![]);
-        map.insert("charset", vec![]);
-        map
-    };
-    pub static ref FIND_NEARBY_POSTAL_CODES_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("radius", vec![]);
-        map.insert("maxRows", vec![]);
-        map.insert("style", vec!["SHORT", "MEDIUM", "LONG", "FULL"]);
-        map.insert("country", vec![]);
-        map.insert("localCountry", vec![]);
-        map.insert("isReduced", vec!["true", "false"]);
-        map.insert("postalCode", vec![]);
-        map
-    };
-    pub static ref FIND_NEARBY_PLACE_NAME_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("lang", vec![]);
-        map.insert("maxRows", vec![]);
-        map.insert("radius", vec![]);
-        map.insert("style", vec!["SHORT", "MEDIUM", "LONG", "FULL"]);
-        map.insert("localCountry", vec![]);
-        map.insert("cities", vec!["cities1000", "cities5000", "cities15000"]);
-        map
-    };
-    pub static ref FIND_NEARBY_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("featureClass", vec![]);
-        map.insert("featureCode", vec![]);
-        map.insert("radius", vec![]);
-        map.insert("maxRows", vec![]);
-        map.insert("style", vec!["SHORT", "MEDIUM", "LONG", "FULL"]);
-        map
-    };
-    pub static ref COUNTRY_INFO_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("country", vec![]);
-        map.insert("lang", vec![]);
-        map
-    };
-    pub static ref COUNTRY_CODE_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("type", vec![]);
-        map.insert("lang", vec![]);
-        map.insert("radius", vec![]);
-        map
-    };
-    pub static ref COUNTRY_SUBDIVISION_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("lang", vec![]);
-        map.insert("radius", vec![]);
-        map.insert("level", vec![]);
-        map
-    };
-    pub static ref GET_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("geonameId", vec![]);
-        map.insert("lang", vec![]);
-        map.insert("style", vec![]);
-        map
-    };
-    pub static ref OCEAN_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("radius", vec![]);
-        map
-    };
-    pub static ref SRTM1_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map
-    };
-    pub static ref SRTM3_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map
-    };
-    pub static ref ASTERGDEM_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("postalcodes", vec![]);
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map
-    };
-    pub static ref GTOPO30_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map
-    };
-    pub static ref TIMEZONE_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("radius", vec![]);
-        map.insert("lang", vec![]);
-        map.insert("date", vec![]);
-        map
-    };
-    pub static ref CHILDREN_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("geonameId", vec![]);
-        map.insert("maxRows", vec![]);
-        map.insert("hierarchy", vec!["tourism", "geography", "dependency"]);
-        map
+    pub static ref POSTAL_CODE_SEARCH_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("postalcode", ParamSpec::optional(ParamKind::Text));
+        map.insert("postalcode_startsWith", ParamSpec::optional(ParamKind::Text));
+        map.insert("placename", ParamSpec::optional(ParamKind::Text));
+        map.insert("placename_startsWith", ParamSpec::optional(ParamKind::Text));
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("countryBias", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("style", ParamSpec::optional(ParamKind::STYLE));
+        map.insert("operator", ParamSpec::optional(ParamKind::OPERATOR));
+        map.insert("charset", ParamSpec::optional(ParamKind::Text));
+        map.insert("isReduced", ParamSpec::optional(ParamKind::Bool));
+        ParamTable::new(map)
+            .required_one_of(&["postalcode", "postalcode_startsWith", "placename", "placename_startsWith"])
+    };
+    pub static ref POSTAL_CODE_LOOKUP_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("postalcode", ParamSpec::required(ParamKind::Text));
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("charset", ParamSpec::optional(ParamKind::Text));
+        ParamTable::new(map)
+    };
+    pub static ref FIND_NEARBY_POSTAL_CODES_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::optional(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::optional(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("style", ParamSpec::optional(ParamKind::STYLE));
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("localCountry", ParamSpec::optional(ParamKind::Bool));
+        map.insert("isReduced", ParamSpec::optional(ParamKind::Bool));
+        map.insert("postalCode", ParamSpec::optional(ParamKind::Text));
+        ParamTable::new(map)
+            .together(&["lat", "lng"])
+            .exclusive(&["lat", "postalCode"])
+            .required_one_of(&["lat", "postalCode"])
+    };
+    pub static ref FIND_NEARBY_PLACE_NAME_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("style", ParamSpec::optional(ParamKind::STYLE));
+        map.insert("localCountry", ParamSpec::optional(ParamKind::Bool));
+        map.insert("cities", ParamSpec::optional(ParamKind::CITIES));
+        ParamTable::new(map)
+    };
+    pub static ref FIND_NEARBY_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("featureClass", ParamSpec::optional(ParamKind::Text));
+        map.insert("featureCode", ParamSpec::optional(ParamKind::Text));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("style", ParamSpec::optional(ParamKind::STYLE));
+        ParamTable::new(map)
+    };
+    pub static ref COUNTRY_INFO_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        ParamTable::new(map)
+    };
+    pub static ref COUNTRY_CODE_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("type", ParamSpec::optional(ParamKind::Text));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        ParamTable::new(map)
+    };
+    pub static ref COUNTRY_SUBDIVISION_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("level", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+    };
+    pub static ref GET_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("geonameId", ParamSpec::required(ParamKind::ID));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("style", ParamSpec::optional(ParamKind::STYLE));
+        ParamTable::new(map)
+    };
+    pub static ref OCEAN_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        ParamTable::new(map)
+    };
+    pub static ref SRTM1_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        ParamTable::new(map)
+    };
+    pub static ref SRTM3_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        ParamTable::new(map)
+    };
+    pub static ref ASTERGDEM_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("postalcodes", ParamSpec::optional(ParamKind::Text));
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        ParamTable::new(map)
+    };
+    pub static ref GTOPO30_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        ParamTable::new(map)
+    };
+    pub static ref TIMEZONE_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("date", ParamSpec::optional(ParamKind::Date));
+        ParamTable::new(map)
+    };
+    pub static ref CHILDREN_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("geonameId", ParamSpec::required(ParamKind::ID));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("hierarchy", ParamSpec::optional(ParamKind::OneOf(&["tourism", "geography", "dependency"])));
+        ParamTable::new(map)
+    };
+    pub static ref CITIES_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("north", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("south", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("east", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("west", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
     };
-    pub static ref CITIES_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("north", vec![]);
-        map.insert("south", vec![]);
-        map.insert("east", vec![]);
-        map.insert("west", vec![]);
-        map.insert("maxRows", vec![]);
-        map
+    pub static ref CONTAINS_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("geonameId", ParamSpec::required(ParamKind::ID));
+        map.insert("featureClass", ParamSpec::optional(ParamKind::Text));
+        map.insert("featureCode", ParamSpec::optional(ParamKind::Text));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
     };
-    pub static ref CONTAINS_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("geonameId", vec![]);
-        map.insert("featureClass", vec![]);
-        map.insert("featureCode", vec![]);
-        map.insert("maxRows", vec![]);
-        map
-    };
-    pub static ref EARTHQUAKES_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("north", vec![]);
-        map.insert("south", vec![]);
-        map.insert("east", vec![]);
-        map.insert("west", vec![]);
-        map.insert("date", vec![]);
-        map.insert("minMagnitude", vec![]);
-        map.insert("maxRows", vec![]);
-        map
-    };
-    pub static ref FIND_NEARBY_STREETS_0SM_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("radius", vec![]);
-        map.insert("maxRows", vec![]);
-        map
-    };
-    pub static ref FIND_NEARBY_BY_WEATHER_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("radius", vec![]);
-        map
-    };
-    pub static ref FIND_NEARBY_BY_WIKIPEDIA_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lang", vec![]);
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("radius", vec![]);
-        map.insert("maxRows", vec![]);
-        map.insert("country", vec![]);
-        map.insert("postalCode", vec![]);
-        map.insert("length", vec![]);
-        map
-    };
-    pub static ref FIND_NEARBY_POIS_OSM_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("radius", vec![]);
-        map.insert("maxRows", vec![]);
-        map
-    };
-    pub static ref ADDRESS_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("lat", vec![]);
-        map.insert("lng", vec![]);
-        map.insert("radius", vec![]);
-        map
-    };
-    pub static ref GEO_CODE_ADDRESS_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("q", vec![]);
-        map.insert("country", vec![]);
-        map.insert("postalcode", vec![]);
-        map
-    };
-    pub static ref STREET_NAME_LOOKUP_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("q", vec![]);
-        map.insert("country", vec![]);
-        map.insert("postalcode", vec![]);
-        map.insert("adminCode1", vec![]);
-        map.insert("adminCode2", vec![]);
-        map.insert("adminCode3", vec![]);
-        map.insert("isUniqueStreetName", vec![]);
-        map
-    };
-    pub static ref HIERARCHY_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("geonameId", vec![]);
-        map
-    };
-    pub static ref NEIGHBOURS_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("geonameId", vec![]);
-        map.insert("country", vec![]);
-        map
-    };
-    pub static ref SEARCH_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("q", vec![]);
-        map.insert("name", vec![]);
-        map.insert("name_equals", vec![]);
-        map.insert("name_startsWith", vec![]);
-        map.insert("maxRows", vec![]);
-        map.insert("startRow", vec![]);
-        map.insert("country", vec![]);
-        map.insert("countryBias", vec![]);
-        map.insert("continentCode", vec![]);
-        map.insert("adminCode1", vec![]);
-        map.insert("adminCode2", vec![]);
-        map.insert("adminCode3", vec![]);
-        map.insert("adminCode4", vec![]);
-        map.insert("adminCode5", vec![]);
-        map.insert("featureClass", vec![]);
-        map.insert("featureCode", vec![]);
-        map.insert("cities", vec![]);
-        map.insert("lang", vec![]);
-        map.insert("type", vec![]);
-        map.insert("style", vec![]);
-        map.insert("isNameRequired", vec![]);
-        map.insert("tag", vec![]);
-        map.insert("operator", vec![]);
-        map.insert("charset", vec![]);
-        map.insert("fuzzy", vec![]);
-        map.insert("north", vec![]);
-        map.insert("south", vec![]);
-        map.insert("east", vec![]);
-        map.insert("west", vec![]);
-        map.insert("searchlang", vec![]);
-        map.insert("orderby", vec![]);
-        map.insert("inclBbox", vec![]);
-        map
-    };
-    pub static ref SIBLINGS_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("geonameId", vec![]);
-        map
-    };
-    pub static ref WEATHER_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("north", vec![]);
-        map.insert("south", vec![]);
-        map.insert("east", vec![]);
-        map.insert("west", vec![]);
-        map.insert("maxRows", vec![]);
-        map
-    };
-    pub static ref WEATHER_ICAO_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("ICAO", vec![]);
-        map
-    };
-    pub static ref WIKIPEDIA_BOUNDING_BOX_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("north", vec![]);
-        map.insert("south", vec![]);
-        map.insert("east", vec![]);
-        map.insert("west", vec![]);
-        map.insert("lang", vec![]);
-        map.insert("maxRows", vec![]);
-        map
-    };
-    pub static ref WIKIPEDIA_SEARCH_PARAMS: HashMap<&'static str, Vec<&'static str>> = {
-        let mut map = HashMap::new();
-        map.insert("q", vec![]);
-        map.insert("title", vec![]);
-        map.insert("lang", vec![]);
-        map.insert("maxRows", vec![]);
-        map
+    pub static ref EARTHQUAKES_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("north", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("south", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("east", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("west", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("date", ParamSpec::optional(ParamKind::Date));
+        map.insert("minMagnitude", ParamSpec::optional(ParamKind::NUMBER));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+    };
+    pub static ref FIND_NEARBY_STREETS_0SM_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+    };
+    pub static ref FIND_NEARBY_BY_WEATHER_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        ParamTable::new(map)
+    };
+    pub static ref FIND_NEARBY_BY_WIKIPEDIA_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("lat", ParamSpec::optional(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::optional(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("postalCode", ParamSpec::optional(ParamKind::Text));
+        map.insert("length", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+            .together(&["lat", "lng"])
+            .exclusive(&["lat", "postalCode"])
+            .required_one_of(&["lat", "postalCode"])
+    };
+    pub static ref FIND_NEAREST_INTERSECTION_OSM_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+    };
+    pub static ref FIND_NEARBY_POIS_OSM_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+    };
+    pub static ref ADDRESS_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("lat", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("lng", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("radius", ParamSpec::optional(ParamKind::RADIUS));
+        ParamTable::new(map)
+    };
+    pub static ref GEO_CODE_ADDRESS_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("q", ParamSpec::required(ParamKind::Text));
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("postalcode", ParamSpec::optional(ParamKind::Text));
+        ParamTable::new(map)
+    };
+    pub static ref STREET_NAME_LOOKUP_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("q", ParamSpec::required(ParamKind::Text));
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("postalcode", ParamSpec::optional(ParamKind::Text));
+        map.insert("adminCode1", ParamSpec::optional(ParamKind::Text));
+        map.insert("adminCode2", ParamSpec::optional(ParamKind::Text));
+        map.insert("adminCode3", ParamSpec::optional(ParamKind::Text));
+        map.insert("isUniqueStreetName", ParamSpec::optional(ParamKind::Bool));
+        ParamTable::new(map)
+    };
+    pub static ref HIERARCHY_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("geonameId", ParamSpec::required(ParamKind::ID));
+        ParamTable::new(map)
+    };
+    pub static ref NEIGHBOURS_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("geonameId", ParamSpec::optional(ParamKind::ID));
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        ParamTable::new(map)
+            .exclusive(&["geonameId", "country"])
+            .required_one_of(&["geonameId", "country"])
+    };
+    pub static ref SEARCH_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("q", ParamSpec::optional(ParamKind::Text));
+        map.insert("name", ParamSpec::optional(ParamKind::Text));
+        map.insert("name_equals", ParamSpec::optional(ParamKind::Text));
+        map.insert("name_startsWith", ParamSpec::optional(ParamKind::Text));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("startRow", ParamSpec::optional(ParamKind::COUNT));
+        map.insert("country", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("countryBias", ParamSpec::optional(ParamKind::CountryCode));
+        map.insert("continentCode", ParamSpec::optional(ParamKind::OneOf(&["AF", "AS", "EU", "NA", "OC", "SA", "AN"])));
+        map.insert("adminCode1", ParamSpec::optional(ParamKind::Text));
+        map.insert("adminCode2", ParamSpec::optional(ParamKind::Text));
+        map.insert("adminCode3", ParamSpec::optional(ParamKind::Text));
+        map.insert("adminCode4", ParamSpec::optional(ParamKind::Text));
+        map.insert("adminCode5", ParamSpec::optional(ParamKind::Text));
+        map.insert("featureClass", ParamSpec::optional(ParamKind::Text));
+        map.insert("featureCode", ParamSpec::optional(ParamKind::Text));
+        map.insert("cities", ParamSpec::optional(ParamKind::CITIES));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("type", ParamSpec::optional(ParamKind::Text));
+        map.insert("style", ParamSpec::optional(ParamKind::STYLE));
+        map.insert("isNameRequired", ParamSpec::optional(ParamKind::Bool));
+        map.insert("tag", ParamSpec::optional(ParamKind::Text));
+        map.insert("operator", ParamSpec::optional(ParamKind::OPERATOR));
+        map.insert("charset", ParamSpec::optional(ParamKind::Text));
+        map.insert("fuzzy", ParamSpec::optional(ParamKind::Float {
+            min: Some(0.0),
+            max: Some(1.0),
+        }));
+        map.insert("north", ParamSpec::optional(ParamKind::LATITUDE));
+        map.insert("south", ParamSpec::optional(ParamKind::LATITUDE));
+        map.insert("east", ParamSpec::optional(ParamKind::LONGITUDE));
+        map.insert("west", ParamSpec::optional(ParamKind::LONGITUDE));
+        map.insert("searchlang", ParamSpec::optional(ParamKind::Text));
+        map.insert("orderby", ParamSpec::optional(ParamKind::OneOf(&["population", "elevation", "relevance"])));
+        map.insert("inclBbox", ParamSpec::optional(ParamKind::Bool));
+        ParamTable::new(map)
+            .together(&["north", "south", "east", "west"])
+    };
+    pub static ref SIBLINGS_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("geonameId", ParamSpec::required(ParamKind::ID));
+        ParamTable::new(map)
+    };
+    pub static ref WEATHER_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("north", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("south", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("east", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("west", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+    };
+    pub static ref WEATHER_ICAO_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("ICAO", ParamSpec::required(ParamKind::Text));
+        ParamTable::new(map)
+    };
+    pub static ref WIKIPEDIA_BOUNDING_BOX_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("north", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("south", ParamSpec::required(ParamKind::LATITUDE));
+        map.insert("east", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("west", ParamSpec::required(ParamKind::LONGITUDE));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+    };
+    pub static ref WIKIPEDIA_SEARCH_PARAMS: ParamTable = {
+        let mut map = HashMap::new();
+        map.insert("q", ParamSpec::optional(ParamKind::Text));
+        map.insert("title", ParamSpec::optional(ParamKind::Text));
+        map.insert("lang", ParamSpec::optional(ParamKind::Text));
+        map.insert("maxRows", ParamSpec::optional(ParamKind::COUNT));
+        ParamTable::new(map)
+            .required_one_of(&["q", "title"])
     };
 }
 
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::GeoNamesApi;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskCacheConfig {
+    pub dir: PathBuf,
+    pub default_ttl: Duration,
+    pub ttls: HashMap<GeoNamesApi, Duration>,
+    pub max_bytes: u64,
+}
+
+impl DiskCacheConfig {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let minutes = Duration::from_secs(10 * 60);
+
+        let mut ttls = HashMap::new();
+        ttls.insert(GeoNamesApi::Weather, minutes);
+        ttls.insert(GeoNamesApi::WeatherIcao, minutes);
+        ttls.insert(GeoNamesApi::FindNearByWeather, minutes);
+        ttls.insert(GeoNamesApi::Earthquakes, minutes);
+
+        DiskCacheConfig {
+            dir: dir.into(),
+            default_ttl: Duration::from_secs(30 * 24 * 60 * 60),
+            ttls,
+            max_bytes: 100 * 1024 * 1024,
+        }
+    }
+
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    // A zero TTL disables caching for the endpoint
+    pub fn ttl(mut self, api: GeoNamesApi, ttl: Duration) -> Self {
+        self.ttls.insert(api, ttl);
+        self
+    }
+
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn ttl_for(&self, api: GeoNamesApi) -> Duration {
+        self.ttls.get(&api).copied().unwrap_or(self.default_ttl)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct DiskEntry {
+    key: String,
+    stored_at: u64,
+    body: String,
+}
+
+#[derive(Debug)]
+pub(crate) struct DiskCache {
+    config: DiskCacheConfig,
+    // Size of the entries on disk, counted on the first write and kept up to date afterwards so
+    // the directory is only listed when it grows beyond max_bytes
+    total_bytes: Mutex<Option<u64>>,
+}
+
+// Failing to read or write the cache never fails a call, the response is fetched instead. The
+// methods block on file I/O, the client runs them with `spawn_blocking`
+impl DiskCache {
+    pub(crate) fn new(config: DiskCacheConfig) -> Self {
+        DiskCache {
+            config,
+            total_bytes: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn get(&self, api: GeoNamesApi, key: &str) -> Option<Bytes> {
+        let ttl = self.config.ttl_for(api);
+        if ttl.is_zero() {
+            return None;
+        }
+
+        let path = self.path(key);
+        let content = std::fs::read(&path).ok()?;
+        let entry: DiskEntry = serde_json::from_slice(&content).ok()?;
+        if entry.key != key {
+            return None;
+        }
+
+        let ttl_millis = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+        if entry.stored_at.saturating_add(ttl_millis) <= now_millis() {
+            self.remove_file(&path);
+            return None;
+        }
+
+        Some(Bytes::from(entry.body))
+    }
+
+    pub(crate) fn insert(&self, api: GeoNamesApi, key: &str, body: &Bytes) {
+        if self.config.ttl_for(api).is_zero() {
+            return;
+        }
+
+        let entry = DiskEntry {
+            key: key.to_string(),
+            stored_at: now_millis(),
+            body: String::from_utf8_lossy(body).into_owned(),
+        };
+        let Ok(content) = serde_json::to_vec(&entry) else {
+            return;
+        };
+
+        // Written to a temporary file first so readers never see a partial entry
+        let path = self.path(key);
+        let tmp = path.with_extension("tmp");
+        let size = content.len() as u64;
+        let replaced = file_size(&path);
+        let written = std::fs::create_dir_all(&self.config.dir)
+            .and_then(|_| std::fs::write(&tmp, content))
+            .and_then(|_| std::fs::rename(&tmp, &path));
+        if written.is_err() {
+            return;
+        }
+
+        let mut total_bytes = self.total_bytes.lock().unwrap();
+        let total = match *total_bytes {
+            Some(total) => total.saturating_sub(replaced) + size,
+            None => entries(&self.config.dir)
+                .iter()
+                .map(|(_, size, _)| size)
+                .sum(),
+        };
+        *total_bytes = Some(if total > self.config.max_bytes {
+            self.evict()
+        } else {
+            total
+        });
+    }
+
+    pub(crate) fn remove(&self, key: &str) {
+        self.remove_file(&self.path(key));
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut total_bytes = self.total_bytes.lock().unwrap();
+        for (path, _, _) in entries(&self.config.dir) {
+            let _ = std::fs::remove_file(path);
+        }
+        *total_bytes = None;
+    }
+
+    // Removes the oldest entries until the cache is back under 90% of max_bytes, so a full cache
+    // is not listed again on every write, and returns the remaining size
+    fn evict(&self) -> u64 {
+        let mut entries = entries(&self.config.dir);
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        if total <= self.config.max_bytes {
+            return total;
+        }
+
+        let target = self.config.max_bytes / 10 * 9;
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in entries {
+            if total <= target {
+                break;
+            }
+            if std::fs::remove_file(path).is_ok() {
+                total -= size;
+            }
+        }
+        total
+    }
+
+    fn remove_file(&self, path: &Path) {
+        let size = file_size(path);
+        if std::fs::remove_file(path).is_ok() {
+            if let Some(total) = self.total_bytes.lock().unwrap().as_mut() {
+                *total = total.saturating_sub(size);
+            }
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.config.dir.join(format!("{:016x}.json", fnv1a(key)))
+    }
+}
+
+fn entries(dir: &Path) -> Vec<(PathBuf, u64, SystemTime)> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    read_dir
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((
+                entry.path(),
+                metadata.len(),
+                metadata.modified().unwrap_or(UNIX_EPOCH),
+            ))
+        })
+        .collect()
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map_or(0, |metadata| metadata.len())
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+// File names must stay the same across runs and Rust versions, unlike DefaultHasher
+fn fnv1a(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
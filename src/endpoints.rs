@@ -0,0 +1,73 @@
+use serde::de::DeserializeOwned;
+
+use crate::{
+    AddressResponse, AstergdemResponse, ChildrenResponse, CitiesResponse, ContainsResponse,
+    CountryCodeResponse, CountryInfoResponse, CountrySubvisionResponse, EarthquakesResponse,
+    FindNearbyByPoisOsmResponse, FindNearbyByWeatherResponse, FindNearbyByWikipediaResponse,
+    FindNearbyPlaceResponse, FindNearbyPostalCodesResponse, FindNearbyResponse,
+    FindNearbyStreetsOSMResponse, GeoCodeAddressResponse, GeoNamesApi, GetResponse,
+    Gtopo30Response, HierarchyResponse, NeighboursResponse, OceanResponse,
+    PostalCodeCountryInfoResponse, PostalCodeLookupResponse, PostalCodeSearchResponse,
+    SearchResponse, SiblingsResponse, Srtm1Response, Srtm3Response, StreetNameLookupResponse,
+    TimezoneResponse, WeatherIcaoResponse, WeatherResponse, WikipediaBoundingBoxResponse,
+    WikipediaSearchResponse,
+};
+
+pub trait Endpoint {
+    const API: GeoNamesApi;
+    type Response: DeserializeOwned;
+}
+
+macro_rules! endpoints {
+    ($($name:ident => $response:ty,)*) => {
+        $(
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name;
+
+            impl Endpoint for $name {
+                const API: GeoNamesApi = GeoNamesApi::$name;
+                type Response = $response;
+            }
+        )*
+    };
+}
+
+endpoints! {
+    Astergdem => AstergdemResponse,
+    Children => ChildrenResponse,
+    Cities => CitiesResponse,
+    Contains => ContainsResponse,
+    CountryCode => CountryCodeResponse,
+    CountryInfo => CountryInfoResponse,
+    CountrySubdivision => CountrySubvisionResponse,
+    Earthquakes => EarthquakesResponse,
+    FindNearby => FindNearbyResponse,
+    FindNearbyPlaceName => FindNearbyPlaceResponse,
+    FindNearbyPostalCodes => FindNearbyPostalCodesResponse,
+    FindNearbyStreetsOsm => FindNearbyStreetsOSMResponse,
+    FindNearByWeather => FindNearbyByWeatherResponse,
+    FindNearbyWikipedia => FindNearbyByWikipediaResponse,
+    // There is no typed response for this service yet, the raw JSON is returned instead
+    FindNearestIntersectionOsm => serde_json::Value,
+    FindNearbyPoisOsm => FindNearbyByPoisOsmResponse,
+    Address => AddressResponse,
+    GeoCodeAddress => GeoCodeAddressResponse,
+    StreetNameLookup => StreetNameLookupResponse,
+    Get => GetResponse,
+    Gtopo30 => Gtopo30Response,
+    Hierarchy => HierarchyResponse,
+    Neighbours => NeighboursResponse,
+    Ocean => OceanResponse,
+    PostalCodeCountryInfo => PostalCodeCountryInfoResponse,
+    PostalCodeLookup => PostalCodeLookupResponse,
+    PostalCodeSearch => PostalCodeSearchResponse,
+    Search => SearchResponse,
+    Siblings => SiblingsResponse,
+    Srtm1 => Srtm1Response,
+    Srtm3 => Srtm3Response,
+    Timezone => TimezoneResponse,
+    Weather => WeatherResponse,
+    WeatherIcao => WeatherIcaoResponse,
+    WikipediaBoundingBox => WikipediaBoundingBoxResponse,
+    WikipediaSearch => WikipediaSearchResponse,
+}
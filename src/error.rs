@@ -1,7 +1,341 @@
-#[derive(Debug)]
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Url;
+
+use crate::redact::{redact_url, Redactor};
+use crate::response::ApiStatus;
+use crate::GeoNamesApi;
+
+const BODY_SNIPPET_LEN: usize = 256;
+
+#[derive(Debug, Clone)]
 pub enum ApiError {
-    Deserialization(String),
+    Deserialization(RequestError),
     UrlParse(String),
     InvalidParams(String),
     GeonamesApi(String),
+    Config(String),
+    Connection(RequestError),
+    HttpStatus(RequestError),
+    RateLimited(Duration),
+    DailyLimitExceeded(StatusError),
+    HourlyLimitExceeded(StatusError),
+    WeeklyLimitExceeded(StatusError),
+    InvalidUser(StatusError),
+    UserAccountNotEnabled(StatusError),
+    NoResultFound(StatusError),
+    InvalidParameter(StatusError),
+    DatabaseTimeout(StatusError),
+    ServerOverloaded(StatusError),
+    Status(StatusError),
+}
+
+// The request an error belongs to, with the username and token removed from the URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    pub endpoint: GeoNamesApi,
+    pub url: String,
+    pub http_status: Option<u16>,
+}
+
+impl RequestContext {
+    pub fn new(endpoint: GeoNamesApi, url: &Url) -> Self {
+        RequestContext {
+            endpoint,
+            url: redact_url(url),
+            http_status: None,
+        }
+    }
+
+    pub fn with_http_status(mut self, http_status: u16) -> Self {
+        self.http_status = Some(http_status);
+        self
+    }
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let endpoint = serde_variant::to_variant_name(&self.endpoint).unwrap_or_default();
+        write!(f, "endpoint {}, url {}", endpoint, self.url)?;
+        if let Some(http_status) = self.http_status {
+            write!(f, ", HTTP status {}", http_status)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestError {
+    pub message: String,
+    pub context: Option<RequestContext>,
+    pub body_snippet: Option<String>,
+    source: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+impl RequestError {
+    pub fn new(message: impl Into<String>) -> Self {
+        RequestError {
+            message: message.into(),
+            context: None,
+            body_snippet: None,
+            source: None,
+        }
+    }
+
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    pub fn with_body(mut self, body: &[u8]) -> Self {
+        self.body_snippet = Some(snippet(body));
+        self
+    }
+
+    pub fn with_context(mut self, context: RequestContext) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    pub fn http_status(&self) -> Option<u16> {
+        self.context
+            .as_ref()
+            .and_then(|context| context.http_status)
+    }
+
+    // A source that mentions a credential is replaced by its scrubbed message
+    fn redact(mut self, redactor: &Redactor) -> Self {
+        self.message = redactor.redact(&self.message);
+        self.body_snippet = self.body_snippet.map(|body| redactor.redact(&body));
+        if let Some(source) = &self.source {
+            let text = format!("{} {:?}", source, source);
+            if redactor.contains_secret(&text) {
+                self.source = Some(Arc::new(RedactedError(
+                    redactor.redact(&source.to_string()),
+                )));
+            }
+        }
+        self
+    }
+}
+
+#[derive(Debug)]
+struct RedactedError(String);
+
+impl fmt::Display for RedactedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for RedactedError {}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(context) = &self.context {
+            write!(f, " ({})", context)?;
+        }
+        if let Some(body) = &self.body_snippet {
+            write!(f, ", body: {}", body)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusError {
+    pub status: ApiStatus,
+    pub context: Option<RequestContext>,
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "GeoNames error {}: {}",
+            self.status.value, self.status.message
+        )?;
+        if let Some(context) = &self.context {
+            write!(f, " ({})", context)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<ApiStatus> for ApiError {
+    fn from(status: ApiStatus) -> Self {
+        let value = status.value;
+        let not_enabled = status.message.contains("not enabled");
+        let error = StatusError {
+            status,
+            context: None,
+        };
+
+        // https://www.geonames.org/export/webservice-exception.html
+        match value {
+            10 if not_enabled => ApiError::UserAccountNotEnabled(error),
+            10 => ApiError::InvalidUser(error),
+            13 => ApiError::DatabaseTimeout(error),
+            14 | 21 => ApiError::InvalidParameter(error),
+            15 => ApiError::NoResultFound(error),
+            18 => ApiError::DailyLimitExceeded(error),
+            19 => ApiError::HourlyLimitExceeded(error),
+            20 => ApiError::WeeklyLimitExceeded(error),
+            22 => ApiError::ServerOverloaded(error),
+            _ => ApiError::Status(error),
+        }
+    }
+}
+
+impl ApiError {
+    pub fn status(&self) -> Option<&ApiStatus> {
+        self.status_error().map(|error| &error.status)
+    }
+
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            ApiError::Deserialization(error)
+            | ApiError::Connection(error)
+            | ApiError::HttpStatus(error) => error.context.as_ref(),
+            _ => self.status_error().and_then(|error| error.context.as_ref()),
+        }
+    }
+
+    pub fn endpoint(&self) -> Option<GeoNamesApi> {
+        self.context().map(|context| context.endpoint)
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        self.context().map(|context| context.url.as_str())
+    }
+
+    pub fn http_status(&self) -> Option<u16> {
+        self.context().and_then(|context| context.http_status)
+    }
+
+    pub fn body_snippet(&self) -> Option<&str> {
+        match self {
+            ApiError::Deserialization(error) | ApiError::HttpStatus(error) => {
+                error.body_snippet.as_deref()
+            }
+            _ => None,
+        }
+    }
+
+    // Errors created below the client, e.g. by a transport, get the request they belong to
+    pub(crate) fn with_context(mut self, context: RequestContext) -> Self {
+        match &mut self {
+            ApiError::Deserialization(error)
+            | ApiError::Connection(error)
+            | ApiError::HttpStatus(error) => {
+                error.context.get_or_insert(context);
+            }
+            _ => {
+                if let Some(error) = self.status_error_mut() {
+                    error.context.get_or_insert(context);
+                }
+            }
+        }
+        self
+    }
+
+    // Scrubs the username and token from every message, body snippet and source
+    pub(crate) fn redact(self, redactor: &Redactor) -> Self {
+        match self {
+            ApiError::Deserialization(error) => ApiError::Deserialization(error.redact(redactor)),
+            ApiError::Connection(error) => ApiError::Connection(error.redact(redactor)),
+            ApiError::HttpStatus(error) => ApiError::HttpStatus(error.redact(redactor)),
+            ApiError::UrlParse(message) => ApiError::UrlParse(redactor.redact(&message)),
+            ApiError::InvalidParams(message) => ApiError::InvalidParams(redactor.redact(&message)),
+            ApiError::GeonamesApi(message) => ApiError::GeonamesApi(redactor.redact(&message)),
+            ApiError::Config(message) => ApiError::Config(redactor.redact(&message)),
+            mut error => {
+                if let Some(status_error) = error.status_error_mut() {
+                    status_error.status.message = redactor.redact(&status_error.status.message);
+                }
+                error
+            }
+        }
+    }
+
+    fn status_error(&self) -> Option<&StatusError> {
+        match self {
+            ApiError::DailyLimitExceeded(error)
+            | ApiError::HourlyLimitExceeded(error)
+            | ApiError::WeeklyLimitExceeded(error)
+            | ApiError::InvalidUser(error)
+            | ApiError::UserAccountNotEnabled(error)
+            | ApiError::NoResultFound(error)
+            | ApiError::InvalidParameter(error)
+            | ApiError::DatabaseTimeout(error)
+            | ApiError::ServerOverloaded(error)
+            | ApiError::Status(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    fn status_error_mut(&mut self) -> Option<&mut StatusError> {
+        match self {
+            ApiError::DailyLimitExceeded(error)
+            | ApiError::HourlyLimitExceeded(error)
+            | ApiError::WeeklyLimitExceeded(error)
+            | ApiError::InvalidUser(error)
+            | ApiError::UserAccountNotEnabled(error)
+            | ApiError::NoResultFound(error)
+            | ApiError::InvalidParameter(error)
+            | ApiError::DatabaseTimeout(error)
+            | ApiError::ServerOverloaded(error)
+            | ApiError::Status(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Deserialization(error)
+            | ApiError::Connection(error)
+            | ApiError::HttpStatus(error) => error.fmt(f),
+            ApiError::UrlParse(message)
+            | ApiError::InvalidParams(message)
+            | ApiError::GeonamesApi(message)
+            | ApiError::Config(message) => f.write_str(message),
+            ApiError::RateLimited(wait) => write!(
+                f,
+                "Credit budget exhausted, credits are available again in {:?}",
+                wait
+            ),
+            _ => match self.status_error() {
+                Some(error) => error.fmt(f),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+impl Error for ApiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ApiError::Deserialization(error)
+            | ApiError::Connection(error)
+            | ApiError::HttpStatus(error) => error
+                .source
+                .as_ref()
+                .map(|source| source.as_ref() as &(dyn Error + 'static)),
+            _ => None,
+        }
+    }
+}
+
+fn snippet(body: &[u8]) -> String {
+    let body = String::from_utf8_lossy(body);
+    match body.char_indices().nth(BODY_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.into_owned(),
+    }
 }
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostStatus {
+    pub host: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Host {
+    pub(crate) url: String,
+    pub(crate) send_token: bool,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    // None for a cooldown too long to represent, the host then waits for its next success
+    unhealthy_until: Option<Instant>,
+}
+
+impl Health {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.unhealthy_until.is_some_and(|until| until <= now)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct HostPool {
+    hosts: Vec<Host>,
+    cooldown: Duration,
+    health: Mutex<HashMap<String, Health>>,
+}
+
+impl HostPool {
+    pub(crate) fn new(hosts: Vec<Host>, cooldown: Duration) -> Self {
+        HostPool {
+            hosts,
+            cooldown,
+            health: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn primary(&self) -> &str {
+        &self.hosts[0].url
+    }
+
+    // Healthy hosts in configured order, then hosts in cooldown as a last resort
+    pub(crate) fn ordered(&self) -> Vec<Host> {
+        let health = self.health.lock().unwrap();
+        let now = Instant::now();
+        let (healthy, unhealthy): (Vec<&Host>, Vec<&Host>) = self
+            .hosts
+            .iter()
+            .partition(|host| health.get(&host.url).is_none_or(|h| h.is_healthy(now)));
+
+        healthy.into_iter().chain(unhealthy).cloned().collect()
+    }
+
+    pub(crate) fn record_success(&self, host: &str) {
+        self.health.lock().unwrap().remove(host);
+    }
+
+    pub(crate) fn record_failure(&self, host: &str) {
+        let mut health = self.health.lock().unwrap();
+        let entry = health.entry(host.to_string()).or_default();
+        entry.consecutive_failures += 1;
+        entry.unhealthy_until = Instant::now().checked_add(self.cooldown);
+    }
+
+    pub(crate) fn statuses(&self) -> Vec<HostStatus> {
+        let health = self.health.lock().unwrap();
+        let now = Instant::now();
+
+        self.hosts
+            .iter()
+            .map(|host| {
+                let entry = health.get(&host.url);
+                HostStatus {
+                    host: host.url.clone(),
+                    healthy: entry.is_none_or(|h| h.is_healthy(now)),
+                    consecutive_failures: entry.map_or(0, |h| h.consecutive_failures),
+                }
+            })
+            .collect()
+    }
+}
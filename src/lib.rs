@@ -1,9 +1,44 @@
+mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cache;
+mod cassette;
 mod client;
+mod client_config;
 mod config;
+mod disk_cache;
+pub mod endpoints;
 mod error;
+mod hosts;
+mod middleware;
+mod paginate;
+mod params;
+mod rate_limit;
+mod redact;
+mod request;
 mod response;
+mod retry;
+mod telemetry;
+mod transport;
+mod validation;
 
+pub use batch::*;
+pub use cache::*;
+pub use cassette::*;
 pub use client::*;
+pub use client_config::*;
 pub use config::*;
+pub use disk_cache::*;
+pub use endpoints::Endpoint;
 pub use error::*;
+pub use hosts::*;
+pub use middleware::*;
+pub use paginate::*;
+pub use params::*;
+pub use rate_limit::*;
+pub use request::*;
 pub use response::*;
+pub use retry::*;
+pub use telemetry::*;
+pub use transport::*;
+pub use validation::*;
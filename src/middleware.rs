@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::{ApiError, GeoNamesApi, Params};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MiddlewareAction {
+    Continue,
+    // Answers the call with this body, skipping the later layers, the caches and the network
+    Respond(Bytes),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawResponse {
+    pub body: Bytes,
+    // None when the response came from a cache or a middleware
+    pub host: Option<String>,
+    pub status: Option<u16>,
+}
+
+// Runs around every call of an `ApiClient`. Requests pass the layers in the order they were added
+// and responses in reverse, an error from either hook fails the call
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    // Receives the validated params, which can be modified before the request is sent
+    async fn on_request(
+        &self,
+        _api: GeoNamesApi,
+        _params: &mut Params,
+    ) -> Result<MiddlewareAction, ApiError> {
+        Ok(MiddlewareAction::Continue)
+    }
+
+    // Also receives the responses of failed calls, such as a GeoNames `status` body or a server
+    // error, before the call returns their error
+    async fn on_response(
+        &self,
+        _api: GeoNamesApi,
+        _params: &Params,
+        _response: &RawResponse,
+    ) -> Result<(), ApiError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use futures::stream::{self, BoxStream, StreamExt};
+
+use crate::{ApiClient, ApiError, GeoNamesApi, Geoname, Params, SearchResponse};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageOptions {
+    pub page_size: u32,
+    pub max_items: Option<u32>,
+    pub row_limit: u32,
+}
+
+impl Default for PageOptions {
+    // GeoNames returns at most 1000 rows per page and pages up to row 5000 on the free services
+    fn default() -> Self {
+        PageOptions {
+            page_size: 100,
+            max_items: None,
+            row_limit: 5000,
+        }
+    }
+}
+
+impl PageOptions {
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.clamp(1, 1000);
+        self
+    }
+
+    pub fn max_items(mut self, max_items: u32) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    // Premium accounts can page further than the free services
+    pub fn row_limit(mut self, row_limit: u32) -> Self {
+        self.row_limit = row_limit;
+        self
+    }
+}
+
+struct PageState {
+    params: Params,
+    start_row: u32,
+    end_row: u32,
+    done: bool,
+}
+
+impl ApiClient {
+    // startRow and maxRows are set per page, an initial startRow in `params` is respected
+    pub fn search_stream(
+        &self,
+        params: impl Into<Params>,
+        options: PageOptions,
+    ) -> BoxStream<'_, Result<Geoname, ApiError>> {
+        let params = params.into();
+        let start_row: u32 = params
+            .get("startRow")
+            .and_then(|row| row.parse().ok())
+            .unwrap_or(0);
+        let end_row = match options.max_items {
+            Some(max_items) => start_row.saturating_add(max_items),
+            None => u32::MAX,
+        };
+
+        let state = PageState {
+            params,
+            start_row,
+            end_row: end_row.min(options.row_limit),
+            done: false,
+        };
+
+        stream::unfold(state, move |mut state| async move {
+            if state.done || state.start_row >= state.end_row {
+                return None;
+            }
+
+            let rows = options
+                .page_size
+                .max(1)
+                .min(state.end_row - state.start_row);
+            state
+                .params
+                .insert("startRow", state.start_row)
+                .insert("maxRows", rows);
+
+            let page = self
+                .call::<SearchResponse>(GeoNamesApi::Search, Some(state.params.clone()))
+                .await;
+
+            match page {
+                Ok(page) => {
+                    let total = page.total_results_count.max(0) as u32;
+                    state.end_row = state.end_row.min(total);
+                    state.start_row += page.geonames.len() as u32;
+                    state.done = page.geonames.is_empty();
+
+                    let items: Vec<_> = page.geonames.into_iter().map(Ok).collect();
+                    Some((items, state))
+                }
+                Err(error) => {
+                    state.done = true;
+                    Some((vec![Err(error)], state))
+                }
+            }
+        })
+        .flat_map(stream::iter)
+        .boxed()
+    }
+}
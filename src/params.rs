@@ -0,0 +1,105 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, Vec<String>>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Display) -> &mut Self {
+        self.values.insert(key.into(), vec![value.to_string()]);
+        self
+    }
+
+    // Adds another value for the key, GeoNames reads repeated keys such as featureCode as a list
+    pub fn append(&mut self, key: impl Into<String>, value: impl Display) -> &mut Self {
+        self.values
+            .entry(key.into())
+            .or_default()
+            .push(value.to_string());
+        self
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl Display) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    pub fn with_all<I, V>(mut self, key: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Display,
+    {
+        let key = key.into();
+        for value in values {
+            self.append(key.clone(), value);
+        }
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(key)
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+
+    pub fn get_all(&self, key: &str) -> &[String] {
+        self.values.get(key).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Vec<String>> {
+        self.values.remove(key)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().flat_map(|(key, values)| {
+            values
+                .iter()
+                .map(move |value| (key.as_str(), value.as_str()))
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<K: Into<String>, V: Display> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut params = Params::new();
+        for (key, value) in iter {
+            params.append(key, value);
+        }
+        params
+    }
+}
+
+impl<K: Into<String>, V: Display> From<HashMap<K, V>> for Params {
+    fn from(map: HashMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Into<String>, V: Display, const N: usize> From<[(K, V); N]> for Params {
+    fn from(pairs: [(K, V); N]) -> Self {
+        pairs.into_iter().collect()
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{ApiError, GeoNamesApi, Params};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitBehavior {
+    Wait,
+    FailFast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CreditLimit {
+    pub credits: u64,
+    pub per: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditBudget {
+    pub limits: Vec<CreditLimit>,
+    pub behavior: LimitBehavior,
+    pub costs: HashMap<GeoNamesApi, u64>,
+    pub full_style_costs: HashMap<GeoNamesApi, u64>,
+}
+
+impl Default for CreditBudget {
+    // Limits of a free GeoNames account
+    fn default() -> Self {
+        CreditBudget::new().hourly(1000).daily(20000)
+    }
+}
+
+impl CreditBudget {
+    pub fn new() -> Self {
+        let mut costs = HashMap::new();
+        costs.insert(GeoNamesApi::FindNearbyStreetsOsm, 2);
+        costs.insert(GeoNamesApi::FindNearestIntersectionOsm, 2);
+        costs.insert(GeoNamesApi::FindNearbyPoisOsm, 2);
+
+        let mut full_style_costs = HashMap::new();
+        full_style_costs.insert(GeoNamesApi::Search, 2);
+
+        CreditBudget {
+            limits: vec![],
+            behavior: LimitBehavior::Wait,
+            costs,
+            full_style_costs,
+        }
+    }
+
+    pub fn limit(mut self, credits: u64, per: Duration) -> Self {
+        self.limits.push(CreditLimit { credits, per });
+        self
+    }
+
+    pub fn hourly(self, credits: u64) -> Self {
+        self.limit(credits, Duration::from_secs(60 * 60))
+    }
+
+    pub fn daily(self, credits: u64) -> Self {
+        self.limit(credits, Duration::from_secs(24 * 60 * 60))
+    }
+
+    pub fn behavior(mut self, behavior: LimitBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+
+    pub fn cost(mut self, api: GeoNamesApi, credits: u64) -> Self {
+        self.costs.insert(api, credits);
+        self
+    }
+
+    pub fn full_style_cost(mut self, api: GeoNamesApi, credits: u64) -> Self {
+        self.full_style_costs.insert(api, credits);
+        self
+    }
+
+    pub fn credits_for(&self, api: GeoNamesApi, params: Option<&Params>) -> u64 {
+        let full_style = params.is_some_and(|p| p.get("style") == Some("FULL"));
+
+        match self.full_style_costs.get(&api) {
+            Some(credits) if full_style => *credits,
+            _ => self.costs.get(&api).copied().unwrap_or(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreditUsage {
+    pub credits_used: u64,
+    pub requests: u64,
+    pub per_api: HashMap<GeoNamesApi, u64>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    limit: CreditLimit,
+    available: f64,
+}
+
+impl Bucket {
+    fn refill(&mut self, elapsed: Duration) {
+        let rate = self.limit.credits as f64 / self.limit.per.as_secs_f64();
+        self.available =
+            (self.available + elapsed.as_secs_f64() * rate).min(self.limit.credits as f64);
+    }
+
+    fn wait_for(&self, credits: u64) -> Duration {
+        let missing = credits as f64 - self.available;
+        if missing <= 0.0 {
+            return Duration::ZERO;
+        }
+
+        // Windows too long for the wait to be represented never refill in practice
+        let rate = self.limit.credits as f64 / self.limit.per.as_secs_f64();
+        Duration::try_from_secs_f64(missing / rate).unwrap_or(Duration::MAX)
+    }
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    buckets: Vec<Bucket>,
+    refilled_at: Instant,
+    usage: CreditUsage,
+}
+
+#[derive(Debug)]
+pub(crate) struct CreditLimiter {
+    budget: CreditBudget,
+    state: Mutex<LimiterState>,
+}
+
+impl CreditLimiter {
+    pub(crate) fn new(budget: CreditBudget) -> Self {
+        let buckets = budget
+            .limits
+            .iter()
+            .map(|limit| Bucket {
+                limit: *limit,
+                available: limit.credits as f64,
+            })
+            .collect();
+
+        CreditLimiter {
+            budget,
+            state: Mutex::new(LimiterState {
+                buckets,
+                refilled_at: Instant::now(),
+                usage: CreditUsage::default(),
+            }),
+        }
+    }
+
+    // Returns the credits taken for the request
+    pub(crate) async fn acquire(
+        &self,
+        api: GeoNamesApi,
+        params: Option<&Params>,
+    ) -> Result<u64, ApiError> {
+        let credits = self.budget.credits_for(api, params);
+
+        if let Some(limit) = self.budget.limits.iter().find(|l| l.credits < credits) {
+            return Err(ApiError::GeonamesApi(format!(
+                "Request costs {} credits but the budget only allows {} per {:?}",
+                credits, limit.credits, limit.per
+            )));
+        }
+
+        loop {
+            let wait = self.try_acquire(api, credits);
+            if wait.is_zero() {
+                return Ok(credits);
+            }
+
+            match self.budget.behavior {
+                LimitBehavior::Wait => tokio::time::sleep(wait).await,
+                LimitBehavior::FailFast => return Err(ApiError::RateLimited(wait)),
+            }
+        }
+    }
+
+    // Takes the credits and returns zero, or returns how long to wait until they are available
+    fn try_acquire(&self, api: GeoNamesApi, credits: u64) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.refilled_at);
+        state.refilled_at = now;
+
+        for bucket in state.buckets.iter_mut() {
+            bucket.refill(elapsed);
+        }
+
+        let wait = state
+            .buckets
+            .iter()
+            .map(|bucket| bucket.wait_for(credits))
+            .max()
+            .unwrap_or(Duration::ZERO);
+
+        if wait.is_zero() {
+            for bucket in state.buckets.iter_mut() {
+                bucket.available -= credits as f64;
+            }
+            state.usage.credits_used += credits;
+            state.usage.requests += 1;
+            *state.usage.per_api.entry(api).or_default() += credits;
+        }
+
+        wait
+    }
+
+    // Gives back the credits of a request GeoNames did not bill, e.g. to an unreachable host
+    pub(crate) fn refund(&self, api: GeoNamesApi, credits: u64) {
+        let mut state = self.state.lock().unwrap();
+        for bucket in state.buckets.iter_mut() {
+            bucket.available = (bucket.available + credits as f64).min(bucket.limit.credits as f64);
+        }
+        state.usage.credits_used = state.usage.credits_used.saturating_sub(credits);
+        state.usage.requests = state.usage.requests.saturating_sub(1);
+        if let Some(used) = state.usage.per_api.get_mut(&api) {
+            *used = used.saturating_sub(credits);
+        }
+    }
+
+    // GeoNames reported the limit as exceeded, so stop sending until the window refills
+    pub(crate) fn record_limit_error(&self, error: &ApiError) {
+        let per = match error {
+            ApiError::HourlyLimitExceeded(_) => Duration::from_secs(60 * 60),
+            ApiError::DailyLimitExceeded(_) => Duration::from_secs(24 * 60 * 60),
+            _ => return,
+        };
+
+        let mut state = self.state.lock().unwrap();
+        for bucket in state.buckets.iter_mut().filter(|b| b.limit.per == per) {
+            bucket.available = 0.0;
+        }
+    }
+
+    pub(crate) fn usage(&self) -> CreditUsage {
+        self.state.lock().unwrap().usage.clone()
+    }
+
+    pub(crate) fn remaining(&self) -> Vec<(CreditLimit, u64)> {
+        let state = self.state.lock().unwrap();
+        let elapsed = state.refilled_at.elapsed();
+
+        state
+            .buckets
+            .iter()
+            .map(|bucket| {
+                let mut bucket = Bucket {
+                    limit: bucket.limit,
+                    available: bucket.available,
+                };
+                bucket.refill(elapsed);
+                (bucket.limit, bucket.available.max(0.0) as u64)
+            })
+            .collect()
+    }
+}
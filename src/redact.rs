@@ -0,0 +1,109 @@
+use reqwest::Url;
+
+pub(crate) const REDACTED: &str = "REDACTED";
+
+// Replaces the credentials so URLs can be shown in errors and cassettes can be committed
+pub(crate) fn redact_url(url: &Url) -> String {
+    let mut redacted = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| match key.as_ref() {
+            "username" | "token" => (key.into_owned(), REDACTED.to_string()),
+            _ => (key.into_owned(), value.into_owned()),
+        })
+        .collect();
+
+    if !pairs.is_empty() {
+        redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    redacted.to_string()
+}
+
+// Scrubs the credentials of a client from free text such as error messages and response bodies
+#[derive(Clone, Default)]
+pub(crate) struct Redactor {
+    usernames: Vec<String>,
+    tokens: Vec<String>,
+}
+
+impl Redactor {
+    pub(crate) fn new(username: &str, token: Option<&str>) -> Self {
+        Redactor {
+            usernames: variants(username),
+            tokens: token.map(variants).unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn redact(&self, text: &str) -> String {
+        let mut text = text.to_string();
+
+        // Tokens are random, so any occurrence is replaced. Usernames can be ordinary words and
+        // are only replaced as a whole word, so a username like "geo" leaves "geonames" intact
+        for token in &self.tokens {
+            text = text.replace(token.as_str(), REDACTED);
+        }
+        for username in &self.usernames {
+            text = replace_word(&text, username);
+        }
+
+        text
+    }
+
+    pub(crate) fn contains_secret(&self, text: &str) -> bool {
+        self.redact(text) != text
+    }
+}
+
+// The value as given and as it appears in a query string
+fn variants(value: &str) -> Vec<String> {
+    if value.is_empty() {
+        return vec![];
+    }
+
+    let encoded = form_urlencode(value);
+    if encoded == value {
+        vec![value.to_string()]
+    } else {
+        vec![value.to_string(), encoded]
+    }
+}
+
+pub(crate) fn form_urlencode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'*' => {
+                (byte as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn replace_word(text: &str, word: &str) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find(word) {
+        let end = index + word.len();
+        let before = rest[..index]
+            .chars()
+            .next_back()
+            .or_else(|| result.chars().next_back());
+        let after = rest[end..].chars().next();
+
+        result.push_str(&rest[..index]);
+        if before.is_some_and(is_word_char) || after.is_some_and(is_word_char) {
+            result.push_str(word);
+        } else {
+            result.push_str(REDACTED);
+        }
+        rest = &rest[end..];
+    }
+
+    result.push_str(rest);
+    result
+}
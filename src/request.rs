@@ -0,0 +1,444 @@
+use crate::{endpoints, Cities, Endpoint, Hierarchy, Operator, Params, Style};
+
+pub trait GeoNamesRequest {
+    type Endpoint: Endpoint;
+
+    fn into_params(self) -> Option<Params>;
+}
+
+macro_rules! request {
+    (
+        $name:ident => $endpoint:ident;
+        new($($arg:ident: $arg_ty:ty => $arg_key:literal),* $(,)?);
+        $(optional { $($setter:ident: $ty:ty => $key:literal,)* })?
+        $(repeated { $($repeated:ident: $repeated_ty:ty => $repeated_key:literal,)* })?
+    ) => {
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            params: Params,
+        }
+
+        impl $name {
+            #[allow(clippy::new_without_default)]
+            pub fn new($($arg: $arg_ty),*) -> Self {
+                #[allow(unused_mut)]
+                let mut params = Params::new();
+                $(params.insert($arg_key, $arg);)*
+                $name { params }
+            }
+
+            $($(
+                pub fn $setter(mut self, value: $ty) -> Self {
+                    self.params.insert($key, value);
+                    self
+                }
+            )*)?
+
+            $($(
+                pub fn $repeated(mut self, value: $repeated_ty) -> Self {
+                    self.params.append($repeated_key, value);
+                    self
+                }
+            )*)?
+        }
+
+        impl From<$name> for Params {
+            fn from(request: $name) -> Self {
+                request.params
+            }
+        }
+
+        impl GeoNamesRequest for $name {
+            type Endpoint = endpoints::$endpoint;
+
+            fn into_params(self) -> Option<Params> {
+                if self.params.is_empty() {
+                    None
+                } else {
+                    Some(self.params)
+                }
+            }
+        }
+    };
+}
+
+request! {
+    AstergdemRequest => Astergdem;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+}
+
+request! {
+    ChildrenRequest => Children;
+    new(geoname_id: i32 => "geonameId");
+    optional {
+        max_rows: u32 => "maxRows",
+        hierarchy: Hierarchy => "hierarchy",
+    }
+}
+
+request! {
+    CitiesRequest => Cities;
+    new(north: f64 => "north", south: f64 => "south", east: f64 => "east", west: f64 => "west");
+    optional {
+        max_rows: u32 => "maxRows",
+    }
+}
+
+request! {
+    ContainsRequest => Contains;
+    new(geoname_id: i32 => "geonameId");
+    optional {
+        max_rows: u32 => "maxRows",
+    }
+    repeated {
+        feature_class: &str => "featureClass",
+        feature_code: &str => "featureCode",
+    }
+}
+
+request! {
+    CountryCodeRequest => CountryCode;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        lang: &str => "lang",
+        radius: f64 => "radius",
+    }
+}
+
+request! {
+    CountryInfoRequest => CountryInfo;
+    new();
+    optional {
+        lang: &str => "lang",
+    }
+    repeated {
+        country: &str => "country",
+    }
+}
+
+request! {
+    CountrySubdivisionRequest => CountrySubdivision;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        lang: &str => "lang",
+        radius: f64 => "radius",
+        level: u32 => "level",
+    }
+}
+
+request! {
+    EarthquakesRequest => Earthquakes;
+    new(north: f64 => "north", south: f64 => "south", east: f64 => "east", west: f64 => "west");
+    optional {
+        date: &str => "date",
+        min_magnitude: f64 => "minMagnitude",
+        max_rows: u32 => "maxRows",
+    }
+}
+
+request! {
+    FindNearbyRequest => FindNearby;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+        max_rows: u32 => "maxRows",
+        style: Style => "style",
+    }
+    repeated {
+        feature_class: &str => "featureClass",
+        feature_code: &str => "featureCode",
+    }
+}
+
+request! {
+    FindNearbyPlaceNameRequest => FindNearbyPlaceName;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        lang: &str => "lang",
+        max_rows: u32 => "maxRows",
+        radius: f64 => "radius",
+        style: Style => "style",
+        local_country: bool => "localCountry",
+        cities: Cities => "cities",
+    }
+}
+
+request! {
+    FindNearbyPostalCodesRequest => FindNearbyPostalCodes;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+        max_rows: u32 => "maxRows",
+        style: Style => "style",
+        country: &str => "country",
+        local_country: bool => "localCountry",
+        is_reduced: bool => "isReduced",
+    }
+}
+
+impl FindNearbyPostalCodesRequest {
+    pub fn for_postal_code(postal_code: &str, country: &str) -> Self {
+        let params = Params::new()
+            .with("postalCode", postal_code)
+            .with("country", country);
+        FindNearbyPostalCodesRequest { params }
+    }
+}
+
+request! {
+    FindNearbyStreetsOsmRequest => FindNearbyStreetsOsm;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+        max_rows: u32 => "maxRows",
+    }
+}
+
+request! {
+    FindNearByWeatherRequest => FindNearByWeather;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+    }
+}
+
+request! {
+    FindNearbyWikipediaRequest => FindNearbyWikipedia;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        lang: &str => "lang",
+        radius: f64 => "radius",
+        max_rows: u32 => "maxRows",
+        country: &str => "country",
+        length: u32 => "length",
+    }
+}
+
+impl FindNearbyWikipediaRequest {
+    pub fn for_postal_code(postal_code: &str, country: &str) -> Self {
+        let params = Params::new()
+            .with("postalCode", postal_code)
+            .with("country", country);
+        FindNearbyWikipediaRequest { params }
+    }
+}
+
+request! {
+    FindNearestIntersectionOsmRequest => FindNearestIntersectionOsm;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+        max_rows: u32 => "maxRows",
+    }
+}
+
+request! {
+    FindNearbyPoisOsmRequest => FindNearbyPoisOsm;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+        max_rows: u32 => "maxRows",
+    }
+}
+
+request! {
+    AddressRequest => Address;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+    }
+}
+
+request! {
+    GeoCodeAddressRequest => GeoCodeAddress;
+    new(q: &str => "q");
+    optional {
+        country: &str => "country",
+        postal_code: &str => "postalcode",
+    }
+}
+
+request! {
+    StreetNameLookupRequest => StreetNameLookup;
+    new(q: &str => "q");
+    optional {
+        country: &str => "country",
+        postal_code: &str => "postalcode",
+        admin_code1: &str => "adminCode1",
+        admin_code2: &str => "adminCode2",
+        admin_code3: &str => "adminCode3",
+        is_unique_street_name: bool => "isUniqueStreetName",
+    }
+}
+
+request! {
+    GetRequest => Get;
+    new(geoname_id: i32 => "geonameId");
+    optional {
+        lang: &str => "lang",
+        style: Style => "style",
+    }
+}
+
+request! {
+    Gtopo30Request => Gtopo30;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+}
+
+request! {
+    HierarchyRequest => Hierarchy;
+    new(geoname_id: i32 => "geonameId");
+}
+
+request! {
+    NeighboursRequest => Neighbours;
+    new(geoname_id: i32 => "geonameId");
+}
+
+impl NeighboursRequest {
+    pub fn for_country(country: &str) -> Self {
+        NeighboursRequest {
+            params: Params::new().with("country", country),
+        }
+    }
+}
+
+request! {
+    OceanRequest => Ocean;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+    }
+}
+
+request! {
+    PostalCodeCountryInfoRequest => PostalCodeCountryInfo;
+    new();
+}
+
+request! {
+    PostalCodeLookupRequest => PostalCodeLookup;
+    new(postal_code: &str => "postalcode");
+    optional {
+        country: &str => "country",
+        max_rows: u32 => "maxRows",
+        charset: &str => "charset",
+    }
+}
+
+request! {
+    PostalCodeSearchRequest => PostalCodeSearch;
+    new();
+    optional {
+        postal_code: &str => "postalcode",
+        postal_code_starts_with: &str => "postalcode_startsWith",
+        place_name: &str => "placename",
+        place_name_starts_with: &str => "placename_startsWith",
+        country_bias: &str => "countryBias",
+        max_rows: u32 => "maxRows",
+        style: Style => "style",
+        operator: Operator => "operator",
+        charset: &str => "charset",
+        is_reduced: bool => "isReduced",
+    }
+    repeated {
+        country: &str => "country",
+    }
+}
+
+request! {
+    SearchRequest => Search;
+    new();
+    optional {
+        q: &str => "q",
+        name: &str => "name",
+        name_equals: &str => "name_equals",
+        name_starts_with: &str => "name_startsWith",
+        max_rows: u32 => "maxRows",
+        start_row: u32 => "startRow",
+        country_bias: &str => "countryBias",
+        continent_code: &str => "continentCode",
+        admin_code1: &str => "adminCode1",
+        admin_code2: &str => "adminCode2",
+        admin_code3: &str => "adminCode3",
+        admin_code4: &str => "adminCode4",
+        admin_code5: &str => "adminCode5",
+        cities: Cities => "cities",
+        lang: &str => "lang",
+        style: Style => "style",
+        is_name_required: bool => "isNameRequired",
+        tag: &str => "tag",
+        operator: Operator => "operator",
+        charset: &str => "charset",
+        fuzzy: f64 => "fuzzy",
+        north: f64 => "north",
+        south: f64 => "south",
+        east: f64 => "east",
+        west: f64 => "west",
+        search_lang: &str => "searchlang",
+        order_by: &str => "orderby",
+        incl_bbox: bool => "inclBbox",
+    }
+    repeated {
+        country: &str => "country",
+        feature_class: &str => "featureClass",
+        feature_code: &str => "featureCode",
+    }
+}
+
+request! {
+    SiblingsRequest => Siblings;
+    new(geoname_id: i32 => "geonameId");
+}
+
+request! {
+    Srtm1Request => Srtm1;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+}
+
+request! {
+    Srtm3Request => Srtm3;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+}
+
+request! {
+    TimezoneRequest => Timezone;
+    new(lat: f64 => "lat", lng: f64 => "lng");
+    optional {
+        radius: f64 => "radius",
+        lang: &str => "lang",
+        date: &str => "date",
+    }
+}
+
+request! {
+    WeatherRequest => Weather;
+    new(north: f64 => "north", south: f64 => "south", east: f64 => "east", west: f64 => "west");
+    optional {
+        max_rows: u32 => "maxRows",
+    }
+}
+
+request! {
+    WeatherIcaoRequest => WeatherIcao;
+    new(icao: &str => "ICAO");
+}
+
+request! {
+    WikipediaBoundingBoxRequest => WikipediaBoundingBox;
+    new(north: f64 => "north", south: f64 => "south", east: f64 => "east", west: f64 => "west");
+    optional {
+        lang: &str => "lang",
+        max_rows: u32 => "maxRows",
+    }
+}
+
+request! {
+    WikipediaSearchRequest => WikipediaSearch;
+    new(q: &str => "q");
+    optional {
+        title: &str => "title",
+        lang: &str => "lang",
+        max_rows: u32 => "maxRows",
+    }
+}
@@ -727,3 +727,15 @@ pub struct WikipediaSearchGeoname {
     pub lat: f64,
     pub wikipedia_url: String,
 }
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiStatus {
+    pub message: String,
+    pub value: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct StatusEnvelope {
+    pub status: ApiStatus,
+}
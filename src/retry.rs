@@ -0,0 +1,121 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::ApiError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryableError {
+    DatabaseTimeout,
+    ServerOverloaded,
+    Connection,
+    ServerError,
+}
+
+impl RetryableError {
+    pub fn from_error(error: &ApiError) -> Option<Self> {
+        match error {
+            ApiError::DatabaseTimeout(_) => Some(RetryableError::DatabaseTimeout),
+            ApiError::ServerOverloaded(_) => Some(RetryableError::ServerOverloaded),
+            ApiError::Connection(_) => Some(RetryableError::Connection),
+            ApiError::HttpStatus(error) if error.http_status().is_some_and(|s| s >= 500) => {
+                Some(RetryableError::ServerError)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    pub jitter: bool,
+    pub retry_on: Vec<RetryableError>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+            retry_on: vec![
+                RetryableError::DatabaseTimeout,
+                RetryableError::ServerOverloaded,
+                RetryableError::Connection,
+                RetryableError::ServerError,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn retry_on(mut self, retry_on: Vec<RetryableError>) -> Self {
+        self.retry_on = retry_on;
+        self
+    }
+
+    // Permanent errors such as an invalid user or parameter never map to a RetryableError
+    pub fn should_retry(&self, error: &ApiError, attempt: u32) -> bool {
+        attempt < self.max_attempts
+            && RetryableError::from_error(error).is_some_and(|kind| self.retry_on.contains(&kind))
+    }
+
+    // Delay before the given retry, attempt 1 being the first retry
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let seconds = self.initial_backoff.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        let delay = Duration::from_secs_f64(seconds.min(self.max_backoff.as_secs_f64()));
+
+        if self.jitter {
+            // Equal jitter keeps at least half of the delay so retries still back off
+            delay / 2 + (delay / 2).mul_f64(random_fraction())
+        } else {
+            delay
+        }
+    }
+}
+
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    hasher.finish() as f64 / u64::MAX as f64
+}
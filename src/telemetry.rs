@@ -0,0 +1,219 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::redact::Redactor;
+use crate::{ApiError, CreditBudget, GeoNamesApi, Params};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Counter {
+    Calls,
+    CallErrors,
+    HttpRequests,
+    CacheHits,
+    CacheMisses,
+    Retries,
+    Credits,
+}
+
+impl Counter {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Counter::Calls => "geonames_calls_total",
+            Counter::CallErrors => "geonames_call_errors_total",
+            Counter::HttpRequests => "geonames_http_requests_total",
+            Counter::CacheHits => "geonames_cache_hits_total",
+            Counter::CacheMisses => "geonames_cache_misses_total",
+            Counter::Retries => "geonames_retries_total",
+            Counter::Credits => "geonames_credits_total",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Histogram {
+    CallDuration,
+    HttpDuration,
+    ResponseBytes,
+}
+
+impl Histogram {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Histogram::CallDuration => "geonames_call_duration_seconds",
+            Histogram::HttpDuration => "geonames_http_request_duration_seconds",
+            Histogram::ResponseBytes => "geonames_response_bytes",
+        }
+    }
+}
+
+// Receives the metrics of every call, labelled with the web service, e.g. to feed Prometheus
+pub trait MetricsRecorder: Send + Sync {
+    fn increment_counter(&self, counter: Counter, api: GeoNamesApi, value: u64);
+    fn record_histogram(&self, histogram: Histogram, api: GeoNamesApi, value: f64);
+}
+
+// Sends call events to the metrics recorder and, with the `tracing` feature, to the current span
+pub(crate) struct Telemetry {
+    recorder: Option<Arc<dyn MetricsRecorder>>,
+    costs: CreditBudget,
+    // Only needed for the params and errors in tracing events
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    redactor: Redactor,
+}
+
+impl Telemetry {
+    pub(crate) fn new(
+        recorder: Option<Arc<dyn MetricsRecorder>>,
+        costs: CreditBudget,
+        redactor: Redactor,
+    ) -> Self {
+        Telemetry {
+            recorder,
+            costs,
+            redactor,
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn span(&self, api: GeoNamesApi, params: Option<&Params>) -> tracing::Span {
+        let params = params
+            .map(|params| {
+                let pairs: Vec<String> = params
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect();
+                self.redactor.redact(&pairs.join("&"))
+            })
+            .unwrap_or_default();
+
+        tracing::info_span!(
+            "geonames.call",
+            endpoint = serde_variant::to_variant_name(&api).unwrap_or_default(),
+            params = %params,
+            cache = tracing::field::Empty,
+            host = tracing::field::Empty,
+            http_status = tracing::field::Empty,
+            response_bytes = tracing::field::Empty,
+            attempts = tracing::field::Empty,
+            credits = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+            error = tracing::field::Empty,
+        )
+    }
+
+    pub(crate) fn cache_lookup(&self, api: GeoNamesApi, hit: bool) {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("cache", if hit { "hit" } else { "miss" });
+
+        let counter = if hit {
+            Counter::CacheHits
+        } else {
+            Counter::CacheMisses
+        };
+        self.count(counter, api, 1);
+    }
+
+    // `status` is None when the host could not be reached
+    pub(crate) fn http_response(
+        &self,
+        api: GeoNamesApi,
+        params: Option<&Params>,
+        host: &str,
+        status: Option<u16>,
+        bytes: usize,
+        elapsed: Duration,
+    ) -> u64 {
+        // Unreachable hosts and server errors are not billed
+        let credits = match status {
+            Some(status) if status < 500 => self.costs.credits_for(api, params),
+            _ => 0,
+        };
+
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("host", host);
+            if let Some(status) = status {
+                span.record("http_status", status);
+                span.record("response_bytes", bytes);
+            }
+            tracing::debug!(
+                host,
+                http_status = status,
+                response_bytes = bytes,
+                latency_ms = elapsed.as_millis() as u64,
+                "GeoNames request finished"
+            );
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = host;
+
+        self.count(Counter::HttpRequests, api, 1);
+        self.count(Counter::Credits, api, credits);
+        self.histogram(Histogram::HttpDuration, api, elapsed.as_secs_f64());
+        if status.is_some() {
+            self.histogram(Histogram::ResponseBytes, api, bytes as f64);
+        }
+
+        credits
+    }
+
+    pub(crate) fn retry(&self, api: GeoNamesApi, attempt: u32, error: &ApiError) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            attempt,
+            error = %self.redactor.redact(&error.to_string()),
+            "Retrying GeoNames request"
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = (attempt, error);
+
+        self.count(Counter::Retries, api, 1);
+    }
+
+    pub(crate) fn fetch_finished(&self, attempts: u32, credits: u64) {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("attempts", attempts);
+            span.record("credits", credits);
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = (attempts, credits);
+    }
+
+    // Errors reaching this point are already redacted
+    pub(crate) fn call_finished(
+        &self,
+        api: GeoNamesApi,
+        elapsed: Duration,
+        error: Option<&ApiError>,
+    ) {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("latency_ms", elapsed.as_millis() as u64);
+            if let Some(error) = error {
+                span.record("error", tracing::field::display(error));
+            }
+        }
+
+        self.count(Counter::Calls, api, 1);
+        if error.is_some() {
+            self.count(Counter::CallErrors, api, 1);
+        }
+        self.histogram(Histogram::CallDuration, api, elapsed.as_secs_f64());
+    }
+
+    fn count(&self, counter: Counter, api: GeoNamesApi, value: u64) {
+        if let (Some(recorder), true) = (&self.recorder, value > 0) {
+            recorder.increment_counter(counter, api, value);
+        }
+    }
+
+    fn histogram(&self, histogram: Histogram, api: GeoNamesApi, value: f64) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record_histogram(histogram, api, value);
+        }
+    }
+}
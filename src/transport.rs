@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::{Client, Url};
+
+use crate::{ApiError, RequestError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportResponse {
+    pub status: u16,
+    pub body: Bytes,
+}
+
+#[async_trait]
+pub trait Transport: Send + Sync {
+    // Receives the complete request URL, including username and token
+    async fn get(&self, url: Url) -> Result<TransportResponse, ApiError>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn get(&self, url: Url) -> Result<TransportResponse, ApiError> {
+        let response = self.client.get(url).send().await.map_err(|e| {
+            ApiError::Connection(
+                RequestError::new("Geonames api error").with_source(e.without_url()),
+            )
+        })?;
+
+        let status = response.status().as_u16();
+        let body = response.bytes().await.map_err(|e| {
+            ApiError::Connection(
+                RequestError::new("Failed to read response body").with_source(e.without_url()),
+            )
+        })?;
+
+        Ok(TransportResponse { status, body })
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::{ApiError, Params};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamKind {
+    Text,
+    Integer { min: Option<i64>, max: Option<i64> },
+    Float { min: Option<f64>, max: Option<f64> },
+    Bool,
+    CountryCode,
+    Date,
+    OneOf(&'static [&'static str]),
+}
+
+impl ParamKind {
+    pub const COUNT: ParamKind = ParamKind::Integer {
+        min: Some(0),
+        max: None,
+    };
+    pub const ID: ParamKind = ParamKind::Integer {
+        min: Some(1),
+        max: None,
+    };
+    pub const NUMBER: ParamKind = ParamKind::Float {
+        min: None,
+        max: None,
+    };
+    pub const RADIUS: ParamKind = ParamKind::Float {
+        min: Some(0.0),
+        max: None,
+    };
+    pub const LATITUDE: ParamKind = ParamKind::Float {
+        min: Some(-90.0),
+        max: Some(90.0),
+    };
+    pub const LONGITUDE: ParamKind = ParamKind::Float {
+        min: Some(-180.0),
+        max: Some(180.0),
+    };
+    pub const STYLE: ParamKind = ParamKind::OneOf(&["SHORT", "MEDIUM", "LONG", "FULL"]);
+    pub const OPERATOR: ParamKind = ParamKind::OneOf(&["AND", "OR"]);
+    pub const CITIES: ParamKind = ParamKind::OneOf(&["cities1000", "cities5000", "cities15000"]);
+
+    fn check(&self, param: &str, value: &str) -> Result<(), ApiError> {
+        let invalid = |expected: &str| {
+            ApiError::InvalidParams(format!(
+                "Invalid value '{}' for param '{}': expected {}",
+                value, param, expected
+            ))
+        };
+
+        match self {
+            ParamKind::Text => Ok(()),
+            ParamKind::Integer { min, max } => {
+                let number: i64 = value.parse().map_err(|_| invalid("an integer"))?;
+                check_range(param, value, number, *min, *max)
+            }
+            ParamKind::Float { min, max } => {
+                let number: f64 = value.parse().map_err(|_| invalid("a number"))?;
+                if !number.is_finite() {
+                    return Err(invalid("a finite number"));
+                }
+                check_range(param, value, number, *min, *max)
+            }
+            ParamKind::Bool => match value {
+                "true" | "false" => Ok(()),
+                _ => Err(invalid("'true' or 'false'")),
+            },
+            ParamKind::CountryCode => {
+                if value.len() == 2 && value.chars().all(|c| c.is_ascii_alphabetic()) {
+                    Ok(())
+                } else {
+                    Err(invalid("an ISO 3166-1 alpha-2 country code"))
+                }
+            }
+            ParamKind::Date => {
+                if is_date(value) {
+                    Ok(())
+                } else {
+                    Err(invalid("a date formatted as yyyy-MM-dd"))
+                }
+            }
+            ParamKind::OneOf(values) => {
+                if values.contains(&value) {
+                    Ok(())
+                } else {
+                    Err(invalid(&format!("one of {}", quoted(values))))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamSpec {
+    pub required: bool,
+    pub kind: ParamKind,
+}
+
+impl ParamSpec {
+    pub fn required(kind: ParamKind) -> Self {
+        ParamSpec {
+            required: true,
+            kind,
+        }
+    }
+
+    pub fn optional(kind: ParamKind) -> Self {
+        ParamSpec {
+            required: false,
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ParamTable {
+    params: HashMap<&'static str, ParamSpec>,
+    exclusive: Vec<&'static [&'static str]>,
+    required_one_of: Vec<&'static [&'static str]>,
+    together: Vec<&'static [&'static str]>,
+}
+
+impl ParamTable {
+    pub fn new(params: HashMap<&'static str, ParamSpec>) -> Self {
+        ParamTable {
+            params,
+            ..Default::default()
+        }
+    }
+
+    // At most one of the params may be set
+    pub fn exclusive(mut self, group: &'static [&'static str]) -> Self {
+        self.exclusive.push(group);
+        self
+    }
+
+    // At least one of the params must be set
+    pub fn required_one_of(mut self, group: &'static [&'static str]) -> Self {
+        self.required_one_of.push(group);
+        self
+    }
+
+    // Either all or none of the params must be set
+    pub fn together(mut self, group: &'static [&'static str]) -> Self {
+        self.together.push(group);
+        self
+    }
+
+    pub fn get(&self, param: &str) -> Option<&ParamSpec> {
+        self.params.get(param)
+    }
+
+    pub fn contains_key(&self, param: &str) -> bool {
+        self.params.contains_key(param)
+    }
+
+    pub fn validate(&self, params: &Params) -> Result<(), ApiError> {
+        for (param, value) in params.iter() {
+            match self.params.get(param) {
+                Some(spec) => spec.kind.check(param, value)?,
+                None => {
+                    return Err(ApiError::InvalidParams(format!(
+                        "Param '{}' not allowed for this API",
+                        param
+                    )))
+                }
+            }
+        }
+
+        let mut required: Vec<_> = self
+            .params
+            .iter()
+            .filter(|(_, spec)| spec.required)
+            .map(|(param, _)| *param)
+            .collect();
+        required.sort_unstable();
+
+        for param in required {
+            if !params.contains_key(param) {
+                return Err(ApiError::InvalidParams(format!(
+                    "Missing required param '{}'",
+                    param
+                )));
+            }
+        }
+
+        for group in &self.exclusive {
+            if present(group, params) > 1 {
+                return Err(ApiError::InvalidParams(format!(
+                    "Params {} are mutually exclusive",
+                    quoted(group)
+                )));
+            }
+        }
+
+        for group in &self.required_one_of {
+            if present(group, params) == 0 {
+                return Err(ApiError::InvalidParams(format!(
+                    "One of the params {} is required",
+                    quoted(group)
+                )));
+            }
+        }
+
+        for group in &self.together {
+            let count = present(group, params);
+            if count != 0 && count != group.len() {
+                return Err(ApiError::InvalidParams(format!(
+                    "Params {} must be set together",
+                    quoted(group)
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn present(group: &[&str], params: &Params) -> usize {
+    group
+        .iter()
+        .filter(|param| params.contains_key(param))
+        .count()
+}
+
+fn quoted(values: &[&str]) -> String {
+    values
+        .iter()
+        .map(|value| format!("'{}'", value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn check_range<T: PartialOrd + Display>(
+    param: &str,
+    value: &str,
+    number: T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<(), ApiError> {
+    let below = min.as_ref().is_some_and(|min| number < *min);
+    let above = max.as_ref().is_some_and(|max| number > *max);
+    if !below && !above {
+        return Ok(());
+    }
+
+    let range = match (min, max) {
+        (Some(min), Some(max)) => format!("between {} and {}", min, max),
+        (Some(min), None) => format!("at least {}", min),
+        (None, Some(max)) => format!("at most {}", max),
+        (None, None) => unreachable!(),
+    };
+
+    Err(ApiError::InvalidParams(format!(
+        "Value '{}' for param '{}' is out of range: must be {}",
+        value, param, range
+    )))
+}
+
+fn is_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    if parts.len() != 3
+        || parts[0].len() != 4
+        || parts[1].len() != 2
+        || parts[2].len() != 2
+        || !parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return false;
+    }
+
+    let month: u32 = parts[1].parse().unwrap_or(0);
+    let day: u32 = parts[2].parse().unwrap_or(0);
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
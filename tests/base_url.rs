@@ -0,0 +1,96 @@
+mod common;
+
+use common::{ocean_params, unused_url, FixtureServer, OCEAN_BODY};
+use geonames_rs::{ApiClient, ApiError, GeoNamesApi, Ocean, OceanResponse};
+
+#[test]
+fn call_api_custom_base_url() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    let expected_result = OceanResponse {
+        ocean: Ocean {
+            distance: "0".to_string(),
+            geoname_id: 3411923,
+            name: "North Atlantic Ocean".to_string(),
+        },
+    };
+
+    assert_eq!(result, expected_result);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("/oceanJSON?"));
+    assert!(requests[0].contains("lat=40.7834"));
+    assert!(requests[0].contains("username=demo"));
+}
+
+#[test]
+fn call_api_base_url_with_path() {
+    let server = FixtureServer::start(vec![("/geonames/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(format!("{}geonames", server.url))
+        .build()
+        .unwrap();
+
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    assert_eq!(result.ocean.geoname_id, 3411923);
+}
+
+#[test]
+fn call_api_fallback_host() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(unused_url())
+        .fallback_host(server.url.clone())
+        .build()
+        .unwrap();
+
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    assert_eq!(result.ocean.name, "North Atlantic Ocean");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn call_api_status_envelope() {
+    let server = FixtureServer::start(vec![(
+        "/oceanJSON",
+        r#"{"status":{"message":"the hourly limit of 1000 credits for demo has been exceeded.","value":19}}"#,
+    )]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let result: Result<OceanResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    match result {
+        Err(ApiError::HourlyLimitExceeded(error)) => assert_eq!(error.status.value, 19),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn builder_rejects_invalid_base_url() {
+    let result = ApiClient::builder("demo").base_url("not a url").build();
+
+    assert!(matches!(result, Err(ApiError::UrlParse(_))));
+}
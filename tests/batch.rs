@@ -0,0 +1,163 @@
+mod common;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use common::OCEAN_BODY;
+use futures::StreamExt;
+use geonames_rs::{
+    ApiClient, ApiError, BatchOptions, GeoNamesApi, OceanRequest, OceanResponse, Params,
+    RetryPolicy, Transport, TransportResponse,
+};
+use reqwest::Url;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// Answers later inputs faster and tracks how many requests run at once
+#[derive(Clone, Default)]
+struct CountingTransport {
+    running: Arc<AtomicUsize>,
+    max_running: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Transport for CountingTransport {
+    async fn get(&self, url: Url) -> Result<TransportResponse, ApiError> {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_running.fetch_max(running, Ordering::SeqCst);
+
+        let lat: u64 = url
+            .query_pairs()
+            .find(|(key, _)| key == "lat")
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(0);
+        tokio::time::sleep(Duration::from_millis(10 * (10 - lat))).await;
+        self.running.fetch_sub(1, Ordering::SeqCst);
+
+        if lat == 7 {
+            return Ok(TransportResponse {
+                status: 200,
+                body: Bytes::from_static(br#"{"status":{"message":"no ocean","value":15}}"#),
+            });
+        }
+
+        Ok(TransportResponse {
+            status: 200,
+            body: Bytes::from_static(OCEAN_BODY.as_bytes()),
+        })
+    }
+}
+
+fn client(transport: CountingTransport) -> ApiClient {
+    ApiClient::builder("demo")
+        .transport(transport)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn inputs() -> Vec<Params> {
+    (0..10)
+        .map(|lat| Params::new().with("lat", lat).with("lng", 0))
+        .collect()
+}
+
+#[test]
+fn batch_returns_every_input_with_its_result() {
+    let client = client(CountingTransport::default());
+
+    let results: Vec<(Params, Result<OceanResponse, ApiError>)> =
+        tokio::runtime::Runtime::new().unwrap().block_on(
+            client
+                .batch(GeoNamesApi::Ocean, inputs(), BatchOptions::default())
+                .collect(),
+        );
+
+    assert_eq!(results.len(), 10);
+    for (params, result) in results {
+        match params.get("lat") {
+            Some("7") => assert!(matches!(result, Err(ApiError::NoResultFound(_)))),
+            _ => assert!(result.is_ok()),
+        }
+    }
+}
+
+#[test]
+fn batch_limits_concurrency() {
+    let transport = CountingTransport::default();
+    let client = client(transport.clone());
+
+    let results: Vec<(Params, Result<OceanResponse, ApiError>)> =
+        tokio::runtime::Runtime::new().unwrap().block_on(
+            client
+                .batch(
+                    GeoNamesApi::Ocean,
+                    inputs(),
+                    BatchOptions::default().concurrency(3),
+                )
+                .collect(),
+        );
+
+    assert_eq!(results.len(), 10);
+    assert_eq!(transport.max_running.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn ordered_batch_keeps_input_order() {
+    let client = client(CountingTransport::default());
+
+    let results: Vec<(Params, Result<OceanResponse, ApiError>)> =
+        tokio::runtime::Runtime::new().unwrap().block_on(
+            client
+                .batch(
+                    GeoNamesApi::Ocean,
+                    inputs(),
+                    BatchOptions::default().concurrency(10).ordered(true),
+                )
+                .collect(),
+        );
+
+    let lats: Vec<String> = results
+        .iter()
+        .map(|(params, _)| params.get("lat").unwrap().to_string())
+        .collect();
+    let expected: Vec<String> = (0..10).map(|lat| lat.to_string()).collect();
+    assert_eq!(lats, expected);
+}
+
+#[test]
+fn unordered_batch_yields_in_completion_order() {
+    let client = client(CountingTransport::default());
+
+    let results: Vec<(Params, Result<OceanResponse, ApiError>)> =
+        tokio::runtime::Runtime::new().unwrap().block_on(
+            client
+                .batch(
+                    GeoNamesApi::Ocean,
+                    inputs(),
+                    BatchOptions::default().concurrency(10),
+                )
+                .collect(),
+        );
+
+    assert_eq!(results[0].0.get("lat"), Some("9"));
+}
+
+#[test]
+fn send_batch_uses_typed_requests() {
+    let client = client(CountingTransport::default());
+    let requests = vec![OceanRequest::new(1.0, 0.0), OceanRequest::new(2.0, 0.0)];
+
+    let results: Vec<_> = tokio::runtime::Runtime::new().unwrap().block_on(
+        client
+            .send_batch(requests.clone(), BatchOptions::default().ordered(true))
+            .collect(),
+    );
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, requests[0]);
+    assert_eq!(
+        results[1].1.as_ref().unwrap().ocean.name,
+        "North Atlantic Ocean"
+    );
+}
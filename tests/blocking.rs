@@ -0,0 +1,145 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use common::{ocean_params, unused_url, FixtureServer, OCEAN_BODY};
+use geonames_rs::blocking::ApiClient;
+use geonames_rs::{
+    endpoints, ApiError, GeoNamesApi, OceanRequest, OceanResponse, Params, RetryPolicy,
+};
+use std::time::Duration;
+
+#[test]
+fn blocking_call_without_runtime() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let result: OceanResponse = client
+        .call(GeoNamesApi::Ocean, Some(ocean_params(40.7834)))
+        .unwrap();
+
+    assert_eq!(result.ocean.name, "North Atlantic Ocean");
+    assert_eq!(
+        server.requests(),
+        vec!["/oceanJSON?lat=40.7834&lng=-43.96635&username=demo"]
+    );
+}
+
+#[test]
+fn blocking_send_and_call_endpoint() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let sent = client.send(OceanRequest::new(40.7834, -43.96635)).unwrap();
+    let called = client
+        .call_endpoint::<endpoints::Ocean>(Some(ocean_params(40.7834)))
+        .unwrap();
+
+    assert_eq!(sent, called);
+}
+
+#[test]
+fn blocking_call_validates_params() {
+    let client = ApiClient::new("demo", None);
+
+    let result: Result<OceanResponse, ApiError> =
+        client.call(GeoNamesApi::Ocean, Some(Params::new().with("lat", 1)));
+
+    assert!(matches!(result, Err(ApiError::InvalidParams(_))));
+}
+
+#[test]
+fn blocking_call_retries_and_falls_back() {
+    let server = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![
+            (
+                200,
+                r#"{"status":{"message":"database timeout","value":13}}"#,
+            ),
+            (200, OCEAN_BODY),
+        ],
+    )]);
+    let client = ApiClient::builder("demo")
+        .base_url(unused_url())
+        .fallback_host(server.url.clone())
+        .retry_policy(
+            RetryPolicy::default().backoff(Duration::from_millis(1), Duration::from_millis(5)),
+        )
+        .build()
+        .unwrap();
+
+    let result: OceanResponse = client
+        .call(GeoNamesApi::Ocean, Some(ocean_params(40.7834)))
+        .unwrap();
+
+    assert_eq!(result.ocean.geoname_id, 3411923);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn blocking_call_maps_status_errors() {
+    let server = FixtureServer::start(vec![(
+        "/oceanJSON",
+        r#"{"status":{"message":"user does not exist.","value":10}}"#,
+    )]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let result: Result<OceanResponse, ApiError> =
+        client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834)));
+
+    assert!(matches!(result, Err(ApiError::InvalidUser(_))));
+}
+
+#[test]
+fn blocking_errors_are_redacted() {
+    let client = ApiClient::builder("alice_geo")
+        .token("tok3n-s3cr3t")
+        .base_url(unused_url())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let error = client
+        .call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834)))
+        .unwrap_err();
+    let source = std::error::Error::source(&error).unwrap();
+
+    for text in [
+        error.to_string(),
+        format!("{:?}", error),
+        source.to_string(),
+        format!("{:?}", client),
+    ] {
+        assert!(!text.contains("tok3n-s3cr3t"), "token leaked: {}", text);
+        assert!(!text.contains("alice_geo"), "username leaked: {}", text);
+    }
+}
+
+#[test]
+fn blocking_deserialization_error_names_serving_host_and_status() {
+    let server = FixtureServer::start(vec![("/oceanJSON", r#"{"ocean":{"name":42}}"#)]);
+    let client = ApiClient::builder("demo")
+        .base_url(unused_url())
+        .fallback_host(server.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let error = client
+        .call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834)))
+        .unwrap_err();
+
+    assert!(matches!(error, ApiError::Deserialization(_)));
+    assert!(error.url().unwrap().starts_with(&server.url));
+    assert_eq!(error.http_status(), Some(200));
+}
@@ -0,0 +1,247 @@
+mod common;
+
+use common::{ocean_params, FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiClient, CacheConfig, CacheStats, CreditBudget, GeoNamesApi, LimitBehavior, OceanResponse,
+    Params,
+};
+use std::time::Duration;
+
+const SEARCH_BODY: &str = r#"{"totalResultsCount":0,"geonames":[]}"#;
+
+fn client(server: &FixtureServer, cache: CacheConfig) -> ApiClient {
+    ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .cache(cache)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn repeated_call_is_served_from_cache() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = client(&server, CacheConfig::default());
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let first: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+    let second: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(
+        client.cache_stats(),
+        Some(CacheStats {
+            hits: 1,
+            misses: 1,
+            entries: 1
+        })
+    );
+}
+
+#[test]
+fn cache_key_ignores_param_order() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = client(&server, CacheConfig::default());
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let _: OceanResponse = runtime
+        .block_on(client.call(
+            GeoNamesApi::Ocean,
+            Some(Params::new().with("lat", 1).with("lng", 2)),
+        ))
+        .unwrap();
+    let _: OceanResponse = runtime
+        .block_on(client.call(
+            GeoNamesApi::Ocean,
+            Some(Params::new().with("lng", 2).with("lat", 1)),
+        ))
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn cache_key_keeps_params_with_separators_apart() {
+    let server = FixtureServer::start(vec![("/searchJSON", SEARCH_BODY)]);
+    let client = client(&server, CacheConfig::default());
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let _: serde_json::Value = runtime
+        .block_on(client.call(
+            GeoNamesApi::Search,
+            Some(Params::new().with("q", "x&searchlang=de")),
+        ))
+        .unwrap();
+    let _: serde_json::Value = runtime
+        .block_on(client.call(
+            GeoNamesApi::Search,
+            Some(Params::new().with("q", "x").with("searchlang", "de")),
+        ))
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn error_responses_are_not_cached() {
+    let server = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![(404, r#"{"error":"not found"}"#), (200, OCEAN_BODY)],
+    )]);
+    let client = client(&server, CacheConfig::default());
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let first: Result<OceanResponse, _> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))));
+    let second: Result<OceanResponse, _> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))));
+
+    assert!(first.is_err());
+    assert!(second.is_ok());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn responses_that_fail_to_deserialize_are_not_cached() {
+    let server = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![(200, r#"{"unexpected":true}"#), (200, OCEAN_BODY)],
+    )]);
+    let client = client(&server, CacheConfig::default());
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let first: Result<OceanResponse, _> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))));
+    let second: Result<OceanResponse, _> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))));
+
+    assert!(first.is_err());
+    assert!(second.is_ok());
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(client.cache_stats().unwrap().entries, 1);
+}
+
+#[test]
+fn expired_entries_are_fetched_again() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let cache = CacheConfig::default().ttl(GeoNamesApi::Ocean, Duration::from_millis(50));
+    let client = client(&server, cache);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn ttl_too_large_for_a_deadline_never_expires() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let cache = CacheConfig::default().ttl(GeoNamesApi::Ocean, Duration::MAX);
+    let client = client(&server, cache);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..2 {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+            .unwrap();
+    }
+
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn zero_ttl_disables_caching_for_endpoint() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let cache = CacheConfig::default().ttl(GeoNamesApi::Ocean, Duration::ZERO);
+    let client = client(&server, cache);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..2 {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+            .unwrap();
+    }
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn least_recently_used_entry_is_evicted() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = client(&server, CacheConfig::new(2));
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for lat in [1.0, 2.0, 1.0, 3.0, 1.0, 2.0] {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(lat))))
+            .unwrap();
+    }
+
+    // 2.0 was evicted by 3.0 because 1.0 had been used more recently
+    let lats: Vec<String> = server
+        .requests()
+        .iter()
+        .map(|target| target.split('&').next().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        lats,
+        vec![
+            "/oceanJSON?lat=1",
+            "/oceanJSON?lat=2",
+            "/oceanJSON?lat=3",
+            "/oceanJSON?lat=2"
+        ]
+    );
+    assert_eq!(client.cache_stats().unwrap().entries, 2);
+}
+
+#[test]
+fn cache_hits_do_not_consume_credits() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .cache(CacheConfig::default())
+        .credit_budget(
+            CreditBudget::new()
+                .limit(1, Duration::from_secs(60))
+                .behavior(LimitBehavior::FailFast),
+        )
+        .build()
+        .unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..3 {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+            .unwrap();
+    }
+
+    assert_eq!(client.credit_usage().unwrap().credits_used, 1);
+}
+
+#[test]
+fn clear_cache_removes_entries() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = client(&server, CacheConfig::default());
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+        .unwrap();
+    client.clear_cache();
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::{FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiClient, ApiError, Cassette, CassetteMode, CassetteTransport, Interaction, OceanRequest,
+    OceanResponse, ReqwestTransport, RetryPolicy,
+};
+use std::path::PathBuf;
+
+fn cassette_path(test: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("geonames-rs-{}", std::process::id()))
+        .join(format!("{}.json", test))
+}
+
+#[test]
+fn record_saves_redacted_interactions() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let path = cassette_path("record");
+    let client = ApiClient::builder("secret-user")
+        .token("secret-token")
+        .base_url(server.url.clone())
+        .cassette(CassetteMode::Record(path.clone()))
+        .build()
+        .unwrap();
+
+    let result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(40.7834, -43.96635)))
+        .unwrap();
+    assert_eq!(result.ocean.name, "North Atlantic Ocean");
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(!content.contains("secret-user"));
+    assert!(!content.contains("secret-token"));
+
+    let cassette = Cassette::load(&path).unwrap();
+    assert_eq!(
+        cassette.interactions,
+        vec![Interaction {
+            url: format!(
+                "{}oceanJSON?lat=40.7834&lng=-43.96635&token=REDACTED&username=REDACTED",
+                server.url
+            ),
+            status: 200,
+            body: OCEAN_BODY.to_string(),
+        }]
+    );
+}
+
+#[test]
+fn replay_serves_recorded_responses_for_any_user() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let path = cassette_path("replay");
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let recorder = ApiClient::builder("recording-user")
+        .base_url(server.url.clone())
+        .transport(CassetteTransport::record(
+            path.clone(),
+            ReqwestTransport::default(),
+        ))
+        .build()
+        .unwrap();
+    let recorded = runtime
+        .block_on(recorder.send(OceanRequest::new(40.7834, -43.96635)))
+        .unwrap();
+
+    let replayer = ApiClient::builder("other-user")
+        .base_url(server.url.clone())
+        .cassette(CassetteMode::Replay(path))
+        .build()
+        .unwrap();
+    let replayed = runtime
+        .block_on(replayer.send(OceanRequest::new(40.7834, -43.96635)))
+        .unwrap();
+
+    assert_eq!(recorded, replayed);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn replay_fails_on_unmatched_request() {
+    let client = ApiClient::builder("demo")
+        .base_url("http://127.0.0.1:1/")
+        .retry_policy(RetryPolicy::none())
+        .transport(CassetteTransport::from_cassette(Cassette::default()))
+        .build()
+        .unwrap();
+
+    let result: Result<OceanResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(1.0, 2.0)));
+
+    match result {
+        Err(ApiError::GeonamesApi(message)) => {
+            assert!(
+                message.starts_with("No recorded response for"),
+                "{}",
+                message
+            )
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn replay_requires_existing_cassette() {
+    let result = ApiClient::builder("demo")
+        .cassette(CassetteMode::Replay(cassette_path("missing")))
+        .build();
+
+    assert!(matches!(result, Err(ApiError::GeonamesApi(_))));
+}
+
+#[test]
+fn call_with_status_body_is_replayed_as_error() {
+    let cassette = Cassette {
+        interactions: vec![Interaction {
+            url: "https://secure.geonames.org/oceanJSON?lat=1&lng=2&username=REDACTED".to_string(),
+            status: 200,
+            body: r#"{"status":{"message":"no ocean","value":15}}"#.to_string(),
+        }],
+    };
+    let client = ApiClient::builder("demo")
+        .transport(CassetteTransport::from_cassette(cassette))
+        .build()
+        .unwrap();
+
+    let result: Result<OceanResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(1.0, 2.0)));
+
+    assert!(matches!(result, Err(ApiError::NoResultFound(_))));
+}
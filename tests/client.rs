@@ -0,0 +1,176 @@
+mod common;
+
+use common::{FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiClient, ApiEndpoint, ApiError, CountryCodeResponse, GeoNamesApi, OceanResponse, Params,
+    TimezoneResponse,
+};
+use std::time::Duration;
+
+const COUNTRY_CODE_BODY: &str =
+    r#"{"languages":"de","distance":"0","countryCode":"DE","countryName":"Germany"}"#;
+const TIMEZONE_BODY: &str = r#"{"sunrise":"2023-12-22 08:15","lng":10.2,"countryCode":"DE","gmtOffset":1,"rawOffset":1,"sunset":"2023-12-22 16:19","timezoneId":"Europe/Berlin","dstOffset":2,"countryName":"Germany","time":"2023-12-22 11:09","lat":50.01}"#;
+
+fn lat_lng() -> Params {
+    Params::new().with("lat", 50.01).with("lng", 10.2)
+}
+
+#[test]
+fn call_multiple_endpoints_with_one_client() {
+    let server = FixtureServer::start(vec![
+        ("/oceanJSON", OCEAN_BODY),
+        ("/countryCodeJSON", COUNTRY_CODE_BODY),
+        ("/timezoneJSON", TIMEZONE_BODY),
+    ]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let ocean: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(lat_lng())))
+        .unwrap();
+    let country: CountryCodeResponse = runtime
+        .block_on(client.call(GeoNamesApi::CountryCode, Some(lat_lng())))
+        .unwrap();
+    let timezone: TimezoneResponse = runtime
+        .block_on(client.call(GeoNamesApi::Timezone, Some(lat_lng())))
+        .unwrap();
+
+    assert_eq!(ocean.ocean.name, "North Atlantic Ocean");
+    assert_eq!(country.country_code, "DE");
+    assert_eq!(timezone.timezone_id, "Europe/Berlin");
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn call_rejects_param_not_allowed_for_endpoint() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+    let mut params = lat_lng();
+    params.insert("maxRows", "1");
+
+    let result: Result<OceanResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(params)));
+
+    assert!(matches!(result, Err(ApiError::InvalidParams(_))));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn allowed_params_per_endpoint() {
+    assert!(GeoNamesApi::Ocean
+        .allowed_params()
+        .unwrap()
+        .contains_key("lat"));
+    assert!(GeoNamesApi::Search
+        .allowed_params()
+        .unwrap()
+        .contains_key("name_startsWith"));
+    assert!(GeoNamesApi::PostalCodeCountryInfo
+        .allowed_params()
+        .is_none());
+}
+
+#[test]
+fn client_is_clone_send_sync() {
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+    assert_shareable::<ApiClient>();
+}
+
+#[test]
+fn call_from_concurrent_tasks() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .coalesce_requests(false)
+        .build()
+        .unwrap();
+
+    let results = tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move {
+                    client
+                        .call::<OceanResponse>(GeoNamesApi::Ocean, Some(lat_lng()))
+                        .await
+                })
+            })
+            .collect();
+
+        let mut results = vec![];
+        for handle in handles {
+            results.push(handle.await.unwrap());
+        }
+        results
+    });
+
+    assert!(results.iter().all(|result| result.is_ok()));
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[test]
+fn call_sends_user_agent() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(lat_lng())))
+        .unwrap();
+    let user_agent = server
+        .last_request()
+        .unwrap()
+        .header("user-agent")
+        .map(String::from);
+    assert!(user_agent.unwrap().starts_with("geonames-rs/"));
+
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .user_agent("reverse-geocoder/1.0")
+        .timeout(Duration::from_secs(5))
+        .connect_timeout(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(lat_lng())))
+        .unwrap();
+    assert_eq!(
+        server.last_request().unwrap().header("user-agent"),
+        Some("reverse-geocoder/1.0")
+    );
+}
+
+#[test]
+fn call_with_injected_http_client() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let http = reqwest::Client::builder()
+        .user_agent("injected")
+        .build()
+        .unwrap();
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .http_client(http)
+        .build()
+        .unwrap();
+
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(lat_lng())))
+        .unwrap();
+
+    assert_eq!(result.ocean.geoname_id, 3411923);
+    assert_eq!(
+        server.last_request().unwrap().header("user-agent"),
+        Some("injected")
+    );
+}
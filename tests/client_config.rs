@@ -0,0 +1,186 @@
+mod common;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use common::{ocean_params, FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiError, ClientConfig, CreditSettings, GeoNamesApi, LimitBehavior, OceanResponse,
+    RetrySettings,
+};
+
+fn config_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("geonames-rs-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn json_config_builds_client() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let path = config_file(
+        "client.json",
+        &format!(
+            r#"{{
+                "username": "demo",
+                "token": "secret",
+                "base_url": "{}",
+                "timeout_secs": 5,
+                "retry": {{ "max_attempts": 1 }},
+                "cache": {{ "capacity": 100, "ttl_secs": 60 }}
+            }}"#,
+            server.url
+        ),
+    );
+
+    let client = ClientConfig::from_file(&path).unwrap().build().unwrap();
+    let params = ocean_params(40.7834);
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(params)))
+        .unwrap();
+
+    assert_eq!(result.ocean.geoname_id, 3411923);
+    let request = &server.requests()[0];
+    assert!(request.contains("username=demo"));
+    assert!(request.contains("token=secret"));
+    assert!(client.cache_stats().is_some());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_config_covers_all_settings() {
+    let config = ClientConfig::from_toml_str(
+        r#"
+        username = "demo"
+        token = "secret"
+        base_url = "https://geonames.example.com/"
+        fallback_hosts = ["https://backup.example.com/"]
+        free_server_fallback = true
+        timeout_secs = 10
+        connect_timeout_secs = 2
+
+        [retry]
+        max_attempts = 5
+        initial_backoff_ms = 200
+
+        [disk_cache]
+        dir = ".geonames-cache"
+        max_bytes = 1048576
+
+        [credits]
+        hourly = 500
+        fail_fast = true
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.username, "demo");
+    assert_eq!(config.fallback_hosts, vec!["https://backup.example.com/"]);
+    assert!(config.free_server_fallback);
+    assert_eq!(config.timeout_secs, Some(10));
+
+    let policy = config.retry.as_ref().unwrap().policy();
+    assert_eq!(policy.max_attempts, 5);
+    assert_eq!(
+        policy.initial_backoff,
+        std::time::Duration::from_millis(200)
+    );
+
+    let disk_cache = config.disk_cache.as_ref().unwrap().config();
+    assert_eq!(disk_cache.max_bytes, 1048576);
+
+    let budget = config.credits.as_ref().unwrap().budget();
+    assert_eq!(budget.limits.len(), 1);
+    assert_eq!(budget.limits[0].credits, 500);
+    assert_eq!(budget.behavior, LimitBehavior::FailFast);
+
+    let client = config.build().unwrap();
+    let hosts: Vec<String> = client.host_status().into_iter().map(|s| s.host).collect();
+    assert_eq!(
+        hosts,
+        vec![
+            "https://geonames.example.com/",
+            "https://backup.example.com/",
+            "https://secure.geonames.org/"
+        ]
+    );
+}
+
+#[test]
+fn vars_override_config() {
+    let vars: HashMap<&str, &str> = HashMap::from([
+        ("GEONAMES_USERNAME", "env-user"),
+        ("GEONAMES_TOKEN", "env-token"),
+        (
+            "GEONAMES_FALLBACK_HOSTS",
+            "https://a.example.com/, https://b.example.com/",
+        ),
+        ("GEONAMES_BASE_URL", ""),
+        ("GEONAMES_TIMEOUT_SECS", "7"),
+    ]);
+    let lookup = |name: &str| vars.get(name).map(|value| value.to_string());
+
+    let from_vars = ClientConfig::default().with_vars(lookup).unwrap();
+    let overridden = ClientConfig::from_json_str(
+        r#"{"username": "file-user", "base_url": "https://file.example.com/", "timeout_secs": 3}"#,
+    )
+    .unwrap()
+    .with_vars(lookup)
+    .unwrap();
+    let invalid = ClientConfig::default()
+        .with_vars(|name| (name == "GEONAMES_TIMEOUT_SECS").then(|| "soon".to_string()));
+
+    assert_eq!(from_vars.username, "env-user");
+    assert_eq!(from_vars.token.as_deref(), Some("env-token"));
+    assert_eq!(
+        from_vars.fallback_hosts,
+        vec!["https://a.example.com/", "https://b.example.com/"]
+    );
+    assert_eq!(overridden.username, "env-user");
+    assert_eq!(
+        overridden.base_url.as_deref(),
+        Some("https://file.example.com/")
+    );
+    assert_eq!(overridden.timeout_secs, Some(7));
+    assert!(matches!(invalid, Err(ApiError::Config(_))));
+}
+
+#[test]
+fn debug_redacts_credentials() {
+    let mut config = ClientConfig::new("demo-user");
+    config.token = Some("secret-token".to_string());
+
+    let debug = format!("{:?}", config);
+
+    assert!(!debug.contains("demo-user"));
+    assert!(!debug.contains("secret-token"));
+    assert!(debug.contains("REDACTED"));
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    let missing_username = ClientConfig::from_json_str("{}").unwrap().build();
+    assert!(matches!(missing_username, Err(ApiError::Config(_))));
+
+    let unknown_field = ClientConfig::from_json_str(r#"{"username": "demo", "tokn": "x"}"#);
+    assert!(matches!(unknown_field, Err(ApiError::Config(_))));
+
+    let path = config_file("client.yaml", "username: demo");
+    assert!(matches!(
+        ClientConfig::from_file(path),
+        Err(ApiError::Config(_))
+    ));
+}
+
+#[test]
+fn credit_settings_default_to_free_account() {
+    let budget = CreditSettings::default().budget();
+    assert_eq!(budget.limits.len(), 2);
+    assert_eq!(budget.behavior, LimitBehavior::Wait);
+
+    let policy = RetrySettings::default().policy();
+    assert_eq!(policy.max_attempts, 3);
+}
@@ -0,0 +1,212 @@
+mod common;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use common::OCEAN_BODY;
+use geonames_rs::{
+    ApiClient, ApiError, GeoNamesApi, OceanRequest, OceanResponse, Params, RetryPolicy, Transport,
+    TransportResponse,
+};
+use reqwest::Url;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone)]
+struct SlowTransport {
+    status: u16,
+    body: &'static str,
+    calls: Arc<AtomicUsize>,
+    urls: Arc<Mutex<Vec<Url>>>,
+}
+
+impl SlowTransport {
+    fn new(status: u16, body: &'static str) -> Self {
+        SlowTransport {
+            status,
+            body,
+            calls: Arc::new(AtomicUsize::new(0)),
+            urls: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn queries(&self) -> Vec<Vec<(String, String)>> {
+        self.urls
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|url| url.query_pairs().into_owned().collect())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Transport for SlowTransport {
+    async fn get(&self, url: Url) -> Result<TransportResponse, ApiError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.urls.lock().unwrap().push(url);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        Ok(TransportResponse {
+            status: self.status,
+            body: Bytes::from_static(self.body.as_bytes()),
+        })
+    }
+}
+
+fn client(transport: SlowTransport, coalesce_requests: bool) -> ApiClient {
+    ApiClient::builder("demo")
+        .transport(transport)
+        .retry_policy(RetryPolicy::none())
+        .coalesce_requests(coalesce_requests)
+        .build()
+        .unwrap()
+}
+
+async fn call_concurrently(
+    client: &ApiClient,
+    requests: Vec<OceanRequest>,
+) -> Vec<Result<OceanResponse, ApiError>> {
+    let handles: Vec<_> = requests
+        .into_iter()
+        .map(|request| {
+            let client = client.clone();
+            tokio::spawn(async move { client.send(request).await })
+        })
+        .collect();
+
+    let mut results = vec![];
+    for handle in handles {
+        results.push(handle.await.unwrap());
+    }
+    results
+}
+
+#[test]
+fn identical_concurrent_calls_share_one_request() {
+    let transport = SlowTransport::new(200, OCEAN_BODY);
+    let client = client(transport.clone(), true);
+
+    let results = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(call_concurrently(
+            &client,
+            vec![OceanRequest::new(1.0, 2.0); 5],
+        ));
+
+    assert_eq!(transport.calls(), 1);
+    assert!(results.iter().all(|result| result.is_ok()));
+}
+
+#[test]
+fn errors_are_shared_with_all_callers() {
+    let transport = SlowTransport::new(
+        200,
+        r#"{"status":{"message":"user does not exist.","value":10}}"#,
+    );
+    let client = client(transport.clone(), true);
+
+    let results = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(call_concurrently(
+            &client,
+            vec![OceanRequest::new(1.0, 2.0); 3],
+        ));
+
+    assert_eq!(transport.calls(), 1);
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(ApiError::InvalidUser(_)))));
+}
+
+#[test]
+fn different_params_are_not_coalesced() {
+    let transport = SlowTransport::new(200, OCEAN_BODY);
+    let client = client(transport.clone(), true);
+
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(call_concurrently(
+            &client,
+            vec![OceanRequest::new(1.0, 2.0), OceanRequest::new(3.0, 4.0)],
+        ));
+
+    assert_eq!(transport.calls(), 2);
+}
+
+#[test]
+fn params_containing_separators_are_not_coalesced() {
+    let transport = SlowTransport::new(200, r#"{"totalResultsCount":0,"geonames":[]}"#);
+    let client = client(transport.clone(), true);
+    let calls = [
+        Params::new().with("q", "x&searchlang=de"),
+        Params::new().with("q", "x").with("searchlang", "de"),
+    ];
+
+    let results = tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let handles: Vec<_> = calls
+            .into_iter()
+            .map(|params| {
+                let client = client.clone();
+                tokio::spawn(async move {
+                    client
+                        .call::<serde_json::Value>(GeoNamesApi::Search, Some(params))
+                        .await
+                })
+            })
+            .collect();
+        futures::future::join_all(handles).await
+    });
+
+    assert!(results.into_iter().all(|result| result.unwrap().is_ok()));
+    assert_eq!(transport.calls(), 2);
+
+    let queries = transport.queries();
+    let searched = |q: &str, searchlang: Option<&str>| {
+        queries.iter().any(|query| {
+            let value = |key: &str| {
+                query
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.as_str())
+            };
+            value("q") == Some(q) && value("searchlang") == searchlang
+        })
+    };
+    assert!(searched("x&searchlang=de", None));
+    assert!(searched("x", Some("de")));
+}
+
+#[test]
+fn sequential_calls_send_new_requests() {
+    let transport = SlowTransport::new(200, OCEAN_BODY);
+    let client = client(transport.clone(), true);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..2 {
+        runtime
+            .block_on(client.send(OceanRequest::new(1.0, 2.0)))
+            .unwrap();
+    }
+
+    assert_eq!(transport.calls(), 2);
+}
+
+#[test]
+fn coalescing_can_be_disabled() {
+    let transport = SlowTransport::new(200, OCEAN_BODY);
+    let client = client(transport.clone(), false);
+
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(call_concurrently(
+            &client,
+            vec![OceanRequest::new(1.0, 2.0); 3],
+        ));
+
+    assert_eq!(transport.calls(), 3);
+}
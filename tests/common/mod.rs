@@ -0,0 +1,137 @@
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use geonames_rs::Params;
+
+pub const OCEAN_BODY: &str =
+    r#"{"ocean":{"distance":"0","geonameId":3411923,"name":"North Atlantic Ocean"}}"#;
+
+pub fn ocean_params(lat: f64) -> Params {
+    Params::new().with("lat", lat).with("lng", -43.96635)
+}
+
+pub struct FixtureServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<FixtureRequest>>>,
+}
+
+#[derive(Clone)]
+pub struct FixtureRequest {
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl FixtureRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl FixtureServer {
+    pub fn start(routes: Vec<(&'static str, &'static str)>) -> Self {
+        Self::start_with_responses(
+            routes
+                .into_iter()
+                .map(|(path, body)| (path, vec![(200, body)]))
+                .collect(),
+        )
+    }
+
+    // Each path answers with its responses in order and keeps repeating the last one
+    pub fn start_with_responses(routes: Vec<(&'static str, Vec<(u16, &'static str)>)>) -> Self {
+        let mut routes: HashMap<&'static str, VecDeque<(u16, &'static str)>> = routes
+            .into_iter()
+            .map(|(path, responses)| (path, responses.into()))
+            .collect();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handle(stream, &mut routes, &recorded);
+            }
+        });
+
+        FixtureServer { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| request.target.clone())
+            .collect()
+    }
+
+    pub fn last_request(&self) -> Option<FixtureRequest> {
+        self.requests.lock().unwrap().last().cloned()
+    }
+}
+
+pub fn unused_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}/", listener.local_addr().unwrap())
+}
+
+fn handle(
+    mut stream: TcpStream,
+    routes: &mut HashMap<&'static str, VecDeque<(u16, &'static str)>>,
+    requests: &Mutex<Vec<FixtureRequest>>,
+) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+
+    let mut headers = vec![];
+    loop {
+        let mut header = String::new();
+        match reader.read_line(&mut header) {
+            Ok(0) => break,
+            Ok(_) if header == "\r\n" => break,
+            Ok(_) => {
+                if let Some((key, value)) = header.split_once(':') {
+                    headers.push((key.trim().to_string(), value.trim().to_string()));
+                }
+            }
+            Err(_) => return,
+        }
+    }
+
+    let target = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("/")
+        .to_string();
+    let path = target.split('?').next().unwrap_or("/");
+    requests.lock().unwrap().push(FixtureRequest {
+        target: target.clone(),
+        headers,
+    });
+
+    let (status, body) = match routes.get_mut(path) {
+        Some(responses) if responses.len() > 1 => responses.pop_front().unwrap(),
+        Some(responses) => responses[0],
+        None => (404, "{\"error\":\"not found\"}"),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {} FIXTURE\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use common::{ocean_params, FixtureServer, OCEAN_BODY};
+use geonames_rs::{ApiClient, DiskCacheConfig, GeoNamesApi, OceanResponse};
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn cache_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("geonames-rs-disk-{}", std::process::id()))
+        .join(test);
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn client(server: &FixtureServer, config: DiskCacheConfig) -> ApiClient {
+    ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .disk_cache(config)
+        .build()
+        .unwrap()
+}
+
+fn cached_files(dir: &PathBuf) -> usize {
+    std::fs::read_dir(dir).map(|d| d.count()).unwrap_or(0)
+}
+
+#[test]
+fn cache_survives_a_new_client() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let dir = cache_dir("restart");
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let first: OceanResponse = runtime
+        .block_on(
+            client(&server, DiskCacheConfig::new(&dir))
+                .call(GeoNamesApi::Ocean, Some(ocean_params(1.0))),
+        )
+        .unwrap();
+    let second: OceanResponse = runtime
+        .block_on(
+            client(&server, DiskCacheConfig::new(&dir))
+                .call(GeoNamesApi::Ocean, Some(ocean_params(1.0))),
+        )
+        .unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(cached_files(&dir), 1);
+}
+
+#[test]
+fn expired_entries_are_fetched_again() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let dir = cache_dir("expiry");
+    let config = DiskCacheConfig::new(&dir).ttl(GeoNamesApi::Ocean, Duration::from_millis(50));
+    let client = client(&server, config);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))))
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn huge_ttl_never_expires() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let dir = cache_dir("huge-ttl");
+    let config = DiskCacheConfig::new(&dir).ttl(GeoNamesApi::Ocean, Duration::MAX);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..2 {
+        let _: OceanResponse = runtime
+            .block_on(
+                client(&server, config.clone()).call(GeoNamesApi::Ocean, Some(ocean_params(1.0))),
+            )
+            .unwrap();
+    }
+
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn invalidate_removes_single_entry() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let dir = cache_dir("invalidate");
+    let client = client(&server, DiskCacheConfig::new(&dir));
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for lat in [1.0, 2.0] {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(lat))))
+            .unwrap();
+    }
+    client.invalidate(GeoNamesApi::Ocean, Some(&ocean_params(1.0)));
+    for lat in [1.0, 2.0] {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(lat))))
+            .unwrap();
+    }
+
+    assert_eq!(server.requests().len(), 3);
+    assert!(server.requests()[2].starts_with("/oceanJSON?lat=1&"));
+}
+
+#[test]
+fn oldest_entries_are_evicted_above_max_bytes() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let dir = cache_dir("eviction");
+    // Each entry holds the ocean body plus its key and timestamp
+    let max_bytes = (OCEAN_BODY.len() * 2 + 300) as u64;
+    let client = client(&server, DiskCacheConfig::new(&dir).max_bytes(max_bytes));
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for lat in [1.0, 2.0, 3.0] {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(lat))))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    assert_eq!(cached_files(&dir), 2);
+
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(3.0))))
+        .unwrap();
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn eviction_counts_entries_from_earlier_runs() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let dir = cache_dir("eviction-restart");
+    let max_bytes = (OCEAN_BODY.len() * 2 + 300) as u64;
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for lat in [1.0, 2.0, 3.0] {
+        let client = client(&server, DiskCacheConfig::new(&dir).max_bytes(max_bytes));
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(lat))))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    assert_eq!(cached_files(&dir), 2);
+}
+
+#[test]
+fn error_responses_are_not_cached() {
+    let server = FixtureServer::start(vec![(
+        "/oceanJSON",
+        r#"{"status":{"message":"no ocean","value":15}}"#,
+    )]);
+    let dir = cache_dir("errors");
+    let client = client(&server, DiskCacheConfig::new(&dir));
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..2 {
+        let result: Result<OceanResponse, _> =
+            runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(1.0))));
+        assert!(result.is_err());
+    }
+
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(cached_files(&dir), 0);
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::{ocean_params, FixtureServer, OCEAN_BODY};
+use geonames_rs::{endpoints, ApiClient, Endpoint, GeoNamesApi, OceanResponse};
+
+#[test]
+fn endpoint_markers_match_api() {
+    assert_eq!(endpoints::Ocean::API, GeoNamesApi::Ocean);
+    assert_eq!(endpoints::Timezone::API, GeoNamesApi::Timezone);
+    assert_eq!(
+        endpoints::FindNearbyStreetsOsm::API,
+        GeoNamesApi::FindNearbyStreetsOsm
+    );
+}
+
+#[test]
+fn call_endpoint_returns_bound_response() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+    let params = ocean_params(40.7834);
+
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call_endpoint::<endpoints::Ocean>(Some(params)))
+        .unwrap();
+
+    assert_eq!(result.ocean.geoname_id, 3411923);
+    assert!(server.requests()[0].starts_with("/oceanJSON?"));
+}
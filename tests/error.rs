@@ -0,0 +1,225 @@
+mod common;
+
+use common::{unused_url, FixtureServer};
+use geonames_rs::{ApiClient, ApiError, ApiStatus, GeoNamesApi, OceanRequest, RetryPolicy};
+use std::error::Error;
+
+fn status(message: &str, value: u32) -> ApiStatus {
+    ApiStatus {
+        message: message.to_string(),
+        value,
+    }
+}
+
+#[test]
+fn status_into_api_error() {
+    assert!(matches!(
+        ApiError::from(status(
+            "the daily limit of 20000 credits has been exceeded",
+            18
+        )),
+        ApiError::DailyLimitExceeded(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status(
+            "the hourly limit of 1000 credits has been exceeded",
+            19
+        )),
+        ApiError::HourlyLimitExceeded(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status("the weekly limit has been exceeded", 20)),
+        ApiError::WeeklyLimitExceeded(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status("user does not exist.", 10)),
+        ApiError::InvalidUser(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status(
+            "user account not enabled to use the free webservice",
+            10
+        )),
+        ApiError::UserAccountNotEnabled(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status("no result found", 15)),
+        ApiError::NoResultFound(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status("invalid lat/lng", 14)),
+        ApiError::InvalidParameter(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status("timeout", 13)),
+        ApiError::DatabaseTimeout(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status("server overloaded", 22)),
+        ApiError::ServerOverloaded(_)
+    ));
+    assert!(matches!(
+        ApiError::from(status("record does not exist", 11)),
+        ApiError::Status(_)
+    ));
+}
+
+#[test]
+fn status_keeps_code_and_message() {
+    let error = ApiError::from(status(
+        "the hourly limit of 1000 credits has been exceeded",
+        19,
+    ));
+
+    assert_eq!(
+        error.status(),
+        Some(&status(
+            "the hourly limit of 1000 credits has been exceeded",
+            19
+        ))
+    );
+    assert_eq!(ApiError::InvalidParams("bad".to_string()).status(), None);
+}
+
+#[test]
+fn api_error_is_std_error() {
+    fn into_boxed() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Err(ApiError::InvalidParams(
+            "Missing required param 'lat'".to_string(),
+        ))?
+    }
+
+    assert_eq!(
+        into_boxed().unwrap_err().to_string(),
+        "Missing required param 'lat'"
+    );
+    assert_eq!(
+        ApiError::from(status("user does not exist.", 10)).to_string(),
+        "GeoNames error 10: user does not exist."
+    );
+}
+
+#[test]
+fn deserialization_error_keeps_context_and_source() {
+    let server = FixtureServer::start(vec![("/oceanJSON", r#"{"ocean":{"name":42}}"#)]);
+    let client = ApiClient::builder("secret-user")
+        .token("secret-token")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let error = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(1.0, 2.0)))
+        .unwrap_err();
+
+    assert!(matches!(error, ApiError::Deserialization(_)));
+    assert_eq!(error.endpoint(), Some(GeoNamesApi::Ocean));
+    assert_eq!(
+        error.url(),
+        Some(
+            format!(
+                "{}oceanJSON?lat=1&lng=2&token=REDACTED&username=REDACTED",
+                server.url
+            )
+            .as_str()
+        )
+    );
+    assert_eq!(error.body_snippet(), Some(r#"{"ocean":{"name":42}}"#));
+    assert_eq!(error.http_status(), Some(200));
+    assert!(error
+        .source()
+        .unwrap()
+        .downcast_ref::<serde_json::Error>()
+        .is_some());
+    assert!(!error.to_string().contains("secret"));
+}
+
+#[test]
+fn error_page_keeps_http_status() {
+    let server = FixtureServer::start(vec![]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let error = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(1.0, 2.0)))
+        .unwrap_err();
+
+    assert!(matches!(error, ApiError::Deserialization(_)));
+    assert_eq!(error.http_status(), Some(404));
+}
+
+#[test]
+fn status_error_records_endpoint_url_and_http_status() {
+    let server = FixtureServer::start(vec![(
+        "/oceanJSON",
+        r#"{"status":{"message":"user does not exist.","value":10}}"#,
+    )]);
+    let client = ApiClient::builder("secret-user")
+        .token("secret-token")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let error = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(1.0, 2.0)))
+        .unwrap_err();
+
+    assert_eq!(error.endpoint(), Some(GeoNamesApi::Ocean));
+    assert_eq!(error.http_status(), Some(200));
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "GeoNames error 10: user does not exist. (endpoint ocean, url {}oceanJSON?lat=1&lng=2&token=REDACTED&username=REDACTED, HTTP status 200)",
+            server.url
+        )
+    );
+}
+
+#[test]
+fn server_error_keeps_status_and_body() {
+    let server = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![(503, "Service Unavailable")],
+    )]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let error = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(1.0, 2.0)))
+        .unwrap_err();
+
+    assert!(matches!(error, ApiError::HttpStatus(_)));
+    assert_eq!(error.http_status(), Some(503));
+    assert_eq!(error.body_snippet(), Some("Service Unavailable"));
+}
+
+#[test]
+fn connection_error_keeps_reqwest_source() {
+    let client = ApiClient::builder("demo")
+        .base_url(unused_url())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let error = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(1.0, 2.0)))
+        .unwrap_err();
+
+    assert!(matches!(error, ApiError::Connection(_)));
+    assert_eq!(error.endpoint(), Some(GeoNamesApi::Ocean));
+    assert!(error
+        .source()
+        .unwrap()
+        .downcast_ref::<reqwest::Error>()
+        .is_some());
+}
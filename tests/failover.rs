@@ -0,0 +1,245 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{ocean_params, unused_url, FixtureServer, OCEAN_BODY};
+use geonames_rs::{ApiClient, CacheConfig, CreditBudget, GeoNamesApi, OceanResponse, RetryPolicy};
+
+#[test]
+fn response_reports_serving_host() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let unreachable = unused_url();
+    let client = ApiClient::builder("demo")
+        .token("secret")
+        .base_url(unreachable.clone())
+        .fallback_host(server.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let response = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(
+            client.call_detailed::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))),
+        )
+        .unwrap();
+
+    assert_eq!(response.host.as_deref(), Some(server.url.as_str()));
+    assert_eq!(response.data.ocean.geoname_id, 3411923);
+}
+
+#[test]
+fn failed_host_is_tried_last_during_cooldown() {
+    let failing = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![(503, "unavailable"), (200, OCEAN_BODY)],
+    )]);
+    let healthy = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(failing.url.clone())
+        .fallback_host(healthy.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..2 {
+        let response = rt
+            .block_on(
+                client.call_detailed::<OceanResponse>(
+                    GeoNamesApi::Ocean,
+                    Some(ocean_params(40.7834)),
+                ),
+            )
+            .unwrap();
+        assert_eq!(response.host.as_deref(), Some(healthy.url.as_str()));
+    }
+
+    assert_eq!(failing.requests().len(), 1);
+    assert_eq!(healthy.requests().len(), 2);
+
+    let status = client.host_status();
+    assert!(!status[0].healthy);
+    assert_eq!(status[0].consecutive_failures, 1);
+    assert!(status[1].healthy);
+}
+
+#[test]
+fn overloaded_host_fails_over() {
+    let overloaded = FixtureServer::start(vec![(
+        "/oceanJSON",
+        r#"{"status":{"message":"the server is overloaded","value":22}}"#,
+    )]);
+    let healthy = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(overloaded.url.clone())
+        .fallback_host(healthy.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let response = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(
+            client.call_detailed::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))),
+        )
+        .unwrap();
+
+    assert_eq!(response.host.as_deref(), Some(healthy.url.as_str()));
+    assert_eq!(overloaded.requests().len(), 1);
+
+    let status = client.host_status();
+    assert!(!status[0].healthy);
+    assert_eq!(status[0].consecutive_failures, 1);
+    assert!(status[1].healthy);
+}
+
+#[test]
+fn failover_charges_credits_for_each_billed_request() {
+    let unreachable = unused_url();
+    let overloaded = FixtureServer::start(vec![(
+        "/oceanJSON",
+        r#"{"status":{"message":"the server is overloaded","value":22}}"#,
+    )]);
+    let healthy = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(unreachable)
+        .fallback_hosts(vec![overloaded.url.clone(), healthy.url.clone()])
+        .retry_policy(RetryPolicy::none())
+        .credit_budget(CreditBudget::new().hourly(10))
+        .build()
+        .unwrap();
+
+    let _: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    // The unreachable host is not billed, the overloaded and the healthy host are
+    let usage = client.credit_usage().unwrap();
+    assert_eq!(usage.credits_used, 2);
+    assert_eq!(usage.requests, 2);
+    assert_eq!(client.remaining_credits()[0].1, 8);
+}
+
+#[test]
+fn unbounded_cooldown_keeps_host_unhealthy() {
+    let failing =
+        FixtureServer::start_with_responses(vec![("/oceanJSON", vec![(503, "unavailable")])]);
+    let healthy = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(failing.url.clone())
+        .fallback_host(healthy.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .host_cooldown(Duration::MAX)
+        .build()
+        .unwrap();
+
+    let _: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    assert!(!client.host_status()[0].healthy);
+}
+
+#[test]
+fn host_recovers_after_cooldown() {
+    let failing = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![(503, "unavailable"), (200, OCEAN_BODY)],
+    )]);
+    let healthy = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(failing.url.clone())
+        .fallback_host(healthy.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .host_cooldown(Duration::from_millis(50))
+        .build()
+        .unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+
+    let response = rt
+        .block_on(
+            client.call_detailed::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))),
+        )
+        .unwrap();
+
+    assert_eq!(response.host.as_deref(), Some(failing.url.as_str()));
+    assert!(client.host_status().iter().all(|status| status.healthy));
+}
+
+#[test]
+fn free_server_fallback_omits_token() {
+    let free = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .token("secret")
+        .base_url(unused_url())
+        .free_server_host(free.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let response = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(
+            client.call_detailed::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))),
+        )
+        .unwrap();
+
+    assert_eq!(response.host.as_deref(), Some(free.url.as_str()));
+    let request = &free.requests()[0];
+    assert!(request.contains("username=demo"));
+    assert!(!request.contains("token"));
+}
+
+#[test]
+fn free_server_fallback_is_opt_in() {
+    let client = ApiClient::builder("demo").token("secret").build().unwrap();
+    let hosts: Vec<String> = client.host_status().into_iter().map(|s| s.host).collect();
+    assert_eq!(hosts, vec!["https://secure.geonames.net/"]);
+
+    let client = ApiClient::builder("demo")
+        .token("secret")
+        .free_server_fallback(true)
+        .build()
+        .unwrap();
+    let hosts: Vec<String> = client.host_status().into_iter().map(|s| s.host).collect();
+    assert_eq!(
+        hosts,
+        vec![
+            "https://secure.geonames.net/",
+            "https://secure.geonames.org/"
+        ]
+    );
+}
+
+#[test]
+fn cache_hit_reports_no_host() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .cache(CacheConfig::new(10))
+        .build()
+        .unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let first = rt
+        .block_on(
+            client.call_detailed::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))),
+        )
+        .unwrap();
+    let second = rt
+        .block_on(
+            client.call_detailed::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))),
+        )
+        .unwrap();
+
+    assert_eq!(first.host.as_deref(), Some(server.url.as_str()));
+    assert_eq!(second.host, None);
+    assert_eq!(first.data, second.data);
+}
@@ -1,6 +1,6 @@
 use geonames_rs::{
-    Address, AddressResponse, AdminCodes1, AlternateName, ApiClient, ApiEndpoint,
-    AstergdemResponse, Bbox, ChildrenResponse, CitiesGeoname, CitiesResponse, ContainsResponse,
+    Address, AddressResponse, AdminCodes1, AlternateName, ApiClient, AstergdemResponse, Bbox,
+    CassetteMode, ChildrenResponse, CitiesGeoname, CitiesResponse, ContainsResponse,
     CountryCodeResponse, CountryInfoGeoname, CountryInfoResponse, CountrySubvisionCode,
     CountrySubvisionResponse, Earthquake, EarthquakesResponse, FindNearbyByPoisOsmResponse,
     FindNearbyByWeatherResponse, FindNearbyByWikipediaResponse, FindNearbyPlaceResponse,
@@ -17,19 +17,32 @@ use geonames_rs::{
     WikipediaSearchResponse,
 };
 use std::collections::HashMap;
+use std::path::PathBuf;
+
+// With API_USER set the tests call the live API and record cassettes, otherwise they are replayed
+fn client(test: &str) -> ApiClient {
+    let path = PathBuf::from("tests/cassettes").join(format!("{}.json", test));
+    let (username, cassette) = match std::env::var("API_USER") {
+        Ok(username) => (username, CassetteMode::Record(path)),
+        Err(_) => ("demo".to_string(), CassetteMode::Replay(path)),
+    };
 
-const USERNAME: &str = env!("API_USER");
+    ApiClient::builder(username)
+        .cassette(cassette)
+        .build()
+        .unwrap()
+}
 
 #[test]
 fn call_api_astergdem() {
-    let client = ApiClient::new(GeoNamesApi::Astergdem, USERNAME, None);
+    let client = client("astergdem");
     let mut params = HashMap::new();
     params.insert("lat", "47.03");
     params.insert("lng", "10.02");
 
     let result: AstergdemResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Astergdem, Some(params.into())))
         .unwrap();
 
     let expected_result = AstergdemResponse {
@@ -43,14 +56,14 @@ fn call_api_astergdem() {
 
 #[test]
 fn call_api_children() {
-    let client = ApiClient::new(GeoNamesApi::Children, USERNAME, None);
+    let client = client("children");
     let mut params = HashMap::new();
     params.insert("geonameId", "3175395");
     params.insert("maxRows", "1");
 
     let result: ChildrenResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Children, Some(params.into())))
         .unwrap();
 
     let expected_result = ChildrenResponse {
@@ -82,7 +95,7 @@ fn call_api_children() {
 
 #[test]
 fn call_api_cities() {
-    let client = ApiClient::new(GeoNamesApi::Cities, USERNAME, None);
+    let client = client("cities");
     let mut params = HashMap::new();
     params.insert("north", "44.1");
     params.insert("south", "-9.9");
@@ -92,7 +105,7 @@ fn call_api_cities() {
 
     let result: CitiesResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Cities, Some(params.into())))
         .unwrap();
 
     let expected_result = CitiesResponse {
@@ -117,14 +130,14 @@ fn call_api_cities() {
 
 #[test]
 fn call_api_contains() {
-    let client = ApiClient::new(GeoNamesApi::Contains, USERNAME, None);
+    let client = client("contains");
     let mut params = HashMap::new();
     params.insert("geonameId", "2746385");
     params.insert("maxRows", "1");
 
     let result: ContainsResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Contains, Some(params.into())))
         .unwrap();
 
     let expected_result = ContainsResponse {
@@ -155,14 +168,14 @@ fn call_api_contains() {
 
 #[test]
 fn call_api_country_code() {
-    let client = ApiClient::new(GeoNamesApi::CountryCode, USERNAME, None);
+    let client = client("country_code");
     let mut params = HashMap::new();
     params.insert("lat", "47.03");
     params.insert("lng", "10.02");
 
     let result: CountryCodeResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::CountryCode, Some(params.into())))
         .unwrap();
 
     let expected_result = CountryCodeResponse {
@@ -176,13 +189,13 @@ fn call_api_country_code() {
 
 #[test]
 fn call_api_country_info() {
-    let client = ApiClient::new(GeoNamesApi::CountryInfo, USERNAME, None);
+    let client = client("country_info");
     let mut params = HashMap::new();
     params.insert("country", "NL");
 
     let result: CountryInfoResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::CountryInfo, Some(params.into())))
         .unwrap();
 
     let expected_result = CountryInfoResponse {
@@ -212,14 +225,14 @@ fn call_api_country_info() {
 
 #[test]
 fn call_api_country_subvision() {
-    let client = ApiClient::new(GeoNamesApi::CountrySubdivision, USERNAME, None);
+    let client = client("country_subvision");
     let mut params = HashMap::new();
     params.insert("lat", "47.03");
     params.insert("lng", "10.2");
 
     let result: CountrySubvisionResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::CountrySubdivision, Some(params.into())))
         .unwrap();
 
     let expected_result = CountrySubvisionResponse {
@@ -239,7 +252,7 @@ fn call_api_country_subvision() {
 
 #[test]
 fn call_api_earthquakes() {
-    let client = ApiClient::new(GeoNamesApi::Earthquakes, USERNAME, None);
+    let client = client("earthquakes");
     let mut params = HashMap::new();
     params.insert("north", "44.1");
     params.insert("south", "-9.9");
@@ -249,7 +262,7 @@ fn call_api_earthquakes() {
 
     let result: EarthquakesResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Earthquakes, Some(params.into())))
         .unwrap();
 
     let expected_result = EarthquakesResponse {
@@ -268,7 +281,7 @@ fn call_api_earthquakes() {
 
 #[test]
 fn call_api_find_nearby() {
-    let client = ApiClient::new(GeoNamesApi::FindNearby, USERNAME, None);
+    let client = client("find_nearby");
     let mut params = HashMap::new();
     params.insert("lat", "47.3");
     params.insert("lng", "9");
@@ -276,7 +289,7 @@ fn call_api_find_nearby() {
 
     let result: FindNearbyResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::FindNearby, Some(params.into())))
         .unwrap();
 
     let expected_result = FindNearbyResponse {
@@ -306,7 +319,7 @@ fn call_api_find_nearby() {
 
 #[test]
 fn call_api_find_nearby_place_name() {
-    let client = ApiClient::new(GeoNamesApi::FindNearbyPlaceName, USERNAME, None);
+    let client = client("find_nearby_place_name");
     let mut params = HashMap::new();
     params.insert("lat", "47.3");
     params.insert("lng", "9");
@@ -314,7 +327,7 @@ fn call_api_find_nearby_place_name() {
 
     let result: FindNearbyPlaceResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::FindNearbyPlaceName, Some(params.into())))
         .unwrap();
 
     let expected_result = FindNearbyPlaceResponse {
@@ -345,7 +358,7 @@ fn call_api_find_nearby_place_name() {
 
 #[test]
 fn call_api_find_nearby_postal_codes() {
-    let client = ApiClient::new(GeoNamesApi::FindNearbyPostalCodes, USERNAME, None);
+    let client = client("find_nearby_postal_codes");
     let mut params = HashMap::new();
     params.insert("lat", "47");
     params.insert("lng", "9");
@@ -353,7 +366,7 @@ fn call_api_find_nearby_postal_codes() {
 
     let result: FindNearbyPostalCodesResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::FindNearbyPostalCodes, Some(params.into())))
         .unwrap();
 
     let expected_result = FindNearbyPostalCodesResponse {
@@ -377,7 +390,7 @@ fn call_api_find_nearby_postal_codes() {
 
 #[test]
 fn call_api_find_nearby_streets_osm() {
-    let client = ApiClient::new(GeoNamesApi::FindNearbyStreetsOsm, USERNAME, None);
+    let client = client("find_nearby_streets_osm");
     let mut params = HashMap::new();
     params.insert("lat", "37.451");
     params.insert("lng", "-122.18");
@@ -385,7 +398,7 @@ fn call_api_find_nearby_streets_osm() {
 
     let result: FindNearbyStreetsOSMResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::FindNearbyStreetsOsm, Some(params.into())))
         .unwrap();
 
     let expected_result = FindNearbyStreetsOSMResponse {
@@ -403,14 +416,14 @@ fn call_api_find_nearby_streets_osm() {
 
 #[test]
 fn call_api_find_nearby_by_weather() {
-    let client = ApiClient::new(GeoNamesApi::FindNearByWeather, USERNAME, None);
+    let client = client("find_nearby_by_weather");
     let mut params = HashMap::new();
     params.insert("lat", "43");
     params.insert("lng", "-2");
 
     let result: FindNearbyByWeatherResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::FindNearByWeather, Some(params.into())))
         .unwrap();
 
     let expected_result = FindNearbyByWeatherResponse {
@@ -443,7 +456,7 @@ fn call_api_find_nearby_by_weather() {
 
 #[test]
 fn call_api_find_nearby_by_wikipedia() {
-    let client = ApiClient::new(GeoNamesApi::FindNearbyWikipedia, USERNAME, None);
+    let client = client("find_nearby_by_wikipedia");
     let mut params = HashMap::new();
     params.insert("lat", "47");
     params.insert("lng", "9");
@@ -451,7 +464,7 @@ fn call_api_find_nearby_by_wikipedia() {
 
     let result: FindNearbyByWikipediaResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::FindNearbyWikipedia, Some(params.into())))
         .unwrap();
 
     let expected_result = FindNearbyByWikipediaResponse {
@@ -475,7 +488,7 @@ fn call_api_find_nearby_by_wikipedia() {
 
 #[test]
 fn call_api_find_nearby_pois_osm() {
-    let client = ApiClient::new(GeoNamesApi::FindNearbyPoisOsm, USERNAME, None);
+    let client = client("find_nearby_pois_osm");
     let mut params = HashMap::new();
     params.insert("lat", "37.451");
     params.insert("lng", "-122.18");
@@ -483,7 +496,7 @@ fn call_api_find_nearby_pois_osm() {
 
     let result: FindNearbyByPoisOsmResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::FindNearbyPoisOsm, Some(params.into())))
         .unwrap();
 
     let expected_result = FindNearbyByPoisOsmResponse {
@@ -501,14 +514,14 @@ fn call_api_find_nearby_pois_osm() {
 
 #[test]
 fn call_api_address() {
-    let client = ApiClient::new(GeoNamesApi::Address, USERNAME, None);
+    let client = client("address");
     let mut params = HashMap::new();
     params.insert("lat", "52.358");
     params.insert("lng", "4.881");
 
     let result: AddressResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Address, Some(params.into())))
         .unwrap();
 
     let expected_result = AddressResponse {
@@ -535,13 +548,13 @@ fn call_api_address() {
 
 #[test]
 fn call_api_geo_code_address() {
-    let client = ApiClient::new(GeoNamesApi::GeoCodeAddress, USERNAME, None);
+    let client = client("geo_code_address");
     let mut params = HashMap::new();
     params.insert("q", "Museumplein 6 amsterdam");
 
     let result: GeoCodeAddressResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::GeoCodeAddress, Some(params.into())))
         .unwrap();
 
     let expected_result = GeoCodeAddressResponse {
@@ -567,7 +580,7 @@ fn call_api_geo_code_address() {
 
 #[test]
 fn call_api_street_name_lookup() {
-    let client = ApiClient::new(GeoNamesApi::StreetNameLookup, USERNAME, None);
+    let client = client("street_name_lookup");
     let mut params = HashMap::new();
     params.insert("q", "Museum");
     params.insert("adminCode2", "59350");
@@ -575,7 +588,7 @@ fn call_api_street_name_lookup() {
 
     let result: StreetNameLookupResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::StreetNameLookup, Some(params.into())))
         .unwrap();
 
     let expected_result = StreetNameLookupResponse {
@@ -600,13 +613,13 @@ fn call_api_street_name_lookup() {
 
 #[test]
 fn call_api_get() {
-    let client = ApiClient::new(GeoNamesApi::Get, USERNAME, None);
+    let client = client("get");
     let mut params = HashMap::new();
     params.insert("geonameId", "2746385");
 
     let result: GetResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Get, Some(params.into())))
         .unwrap();
 
     let expected_result = GetResponse {
@@ -697,14 +710,14 @@ fn call_api_get() {
 
 #[test]
 fn call_api_gtopo30() {
-    let client = ApiClient::new(GeoNamesApi::Gtopo30, USERNAME, None);
+    let client = client("gtopo30");
     let mut params = HashMap::new();
     params.insert("lat", "47.01");
     params.insert("lng", "10.02");
 
     let result: Gtopo30Response = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Gtopo30, Some(params.into())))
         .unwrap();
 
     let expected_result = Gtopo30Response {
@@ -717,13 +730,13 @@ fn call_api_gtopo30() {
 
 #[test]
 fn call_api_hierarchy() {
-    let client = ApiClient::new(GeoNamesApi::Hierarchy, USERNAME, None);
+    let client = client("hierarchy");
     let mut params = HashMap::new();
     params.insert("geonameId", "2657896");
 
     let result: HierarchyResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Hierarchy, Some(params.into())))
         .unwrap();
 
     let expected_result = HierarchyResponse {
@@ -869,13 +882,13 @@ fn call_api_hierarchy() {
 
 #[test]
 fn call_api_neighbours() {
-    let client = ApiClient::new(GeoNamesApi::Neighbours, USERNAME, None);
+    let client = client("neighbours");
     let mut params = HashMap::new();
     params.insert("geonameId", "2658434");
 
     let result: NeighboursResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Neighbours, Some(params.into())))
         .unwrap();
 
     let expected_response = NeighboursResponse {
@@ -973,14 +986,14 @@ fn call_api_neighbours() {
 
 #[test]
 fn call_api_ocean() {
-    let client = ApiClient::new(GeoNamesApi::Ocean, USERNAME, None);
+    let client = client("ocean");
     let mut params = HashMap::new();
     params.insert("lat", "40.7834");
     params.insert("lng", "-43.96635");
 
     let result: OceanResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Ocean, Some(params.into())))
         .unwrap();
 
     let expected_response = OceanResponse {
@@ -995,11 +1008,11 @@ fn call_api_ocean() {
 
 #[test]
 fn call_api_postal_code_country_info() {
-    let client = ApiClient::new(GeoNamesApi::PostalCodeCountryInfo, USERNAME, None);
+    let client = client("postal_code_country_info");
 
     let result: PostalCodeCountryInfoResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(None))
+        .block_on(client.call(GeoNamesApi::PostalCodeCountryInfo, None))
         .unwrap();
 
     let expected_result = PostalCodeCountryInfoResponse {
@@ -1017,7 +1030,7 @@ fn call_api_postal_code_country_info() {
 
 #[test]
 fn call_api_postal_code_lookup() {
-    let client = ApiClient::new(GeoNamesApi::PostalCodeLookup, USERNAME, None);
+    let client = client("postal_code_lookup");
     let mut params = HashMap::new();
     params.insert("postalcode", "6600");
     params.insert("country", "AT");
@@ -1025,7 +1038,7 @@ fn call_api_postal_code_lookup() {
 
     let result: PostalCodeLookupResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::PostalCodeLookup, Some(params.into())))
         .unwrap();
 
     let expected_result = PostalCodeLookupResponse {
@@ -1048,13 +1061,13 @@ fn call_api_postal_code_lookup() {
 
 #[test]
 fn call_api_postal_code_search() {
-    let client = ApiClient::new(GeoNamesApi::PostalCodeSearch, USERNAME, None);
+    let client = client("postal_code_search");
     let mut params = HashMap::new();
     params.insert("postalcode", "1033 SC");
 
     let result: PostalCodeSearchResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::PostalCodeSearch, Some(params.into())))
         .unwrap();
 
     let expected_result = PostalCodeSearchResponse {
@@ -1077,14 +1090,14 @@ fn call_api_postal_code_search() {
 
 #[test]
 fn call_api_search() {
-    let client = ApiClient::new(GeoNamesApi::Search, USERNAME, None);
+    let client = client("search");
     let mut params = HashMap::new();
     params.insert("q", "london");
     params.insert("maxRows", "1");
 
     let result: SearchResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Search, Some(params.into())))
         .unwrap();
 
     let expected_result = SearchResponse {
@@ -1116,13 +1129,13 @@ fn call_api_search() {
 
 #[test]
 fn call_api_siblings() {
-    let client = ApiClient::new(GeoNamesApi::Siblings, USERNAME, None);
+    let client = client("siblings");
     let mut params = HashMap::new();
     params.insert("geonameId", "3017382");
 
     let result: SiblingsResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Siblings, Some(params.into())))
         .unwrap();
 
     let expected_result = SiblingsResponse {
@@ -1155,14 +1168,14 @@ fn call_api_siblings() {
 
 #[test]
 fn call_api_srtm1() {
-    let client = ApiClient::new(GeoNamesApi::Srtm1, USERNAME, None);
+    let client = client("srtm1");
     let mut params = HashMap::new();
     params.insert("lat", "50.01");
     params.insert("lng", "10.2");
 
     let result: Srtm1Response = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Srtm1, Some(params.into())))
         .unwrap();
 
     let expected_result = Srtm1Response {
@@ -1175,14 +1188,14 @@ fn call_api_srtm1() {
 
 #[test]
 fn call_api_srtm3() {
-    let client = ApiClient::new(GeoNamesApi::Srtm3, USERNAME, None);
+    let client = client("srtm3");
     let mut params = HashMap::new();
     params.insert("lat", "50.01");
     params.insert("lng", "10.2");
 
     let result: Srtm3Response = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Srtm3, Some(params.into())))
         .unwrap();
 
     let expected_result = Srtm3Response {
@@ -1195,14 +1208,14 @@ fn call_api_srtm3() {
 
 #[test]
 fn call_api_timezone() {
-    let client = ApiClient::new(GeoNamesApi::Timezone, USERNAME, None);
+    let client = client("timezone");
     let mut params = HashMap::new();
     params.insert("lat", "50.01");
     params.insert("lng", "10.2");
 
     let result: TimezoneResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Timezone, Some(params.into())))
         .unwrap();
 
     let expected_result = TimezoneResponse {
@@ -1223,8 +1236,9 @@ fn call_api_timezone() {
 }
 
 #[test]
+#[allow(clippy::excessive_precision)]
 fn call_api_weather() {
-    let client = ApiClient::new(GeoNamesApi::Weather, USERNAME, None);
+    let client = client("weather");
     let mut params = HashMap::new();
     params.insert("north", "44.1");
     params.insert("south", "-9.9");
@@ -1234,7 +1248,7 @@ fn call_api_weather() {
 
     let _result: WeatherResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::Weather, Some(params.into())))
         .unwrap();
 
     let _expected_result = WeatherResponse {
@@ -1259,13 +1273,13 @@ fn call_api_weather() {
 
 #[test]
 fn call_api_weather_icao() {
-    let client = ApiClient::new(GeoNamesApi::WeatherIcao, USERNAME, None);
+    let client = client("weather_icao");
     let mut params = HashMap::new();
     params.insert("ICAO", "LSZH");
 
     let result: WeatherIcaoResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::WeatherIcao, Some(params.into())))
         .unwrap();
 
     let expected_result = WeatherIcaoResponse {
@@ -1304,7 +1318,7 @@ fn call_api_weather_icao() {
 
 #[test]
 fn call_api_wikipedia_bounding_box() {
-    let client = ApiClient::new(GeoNamesApi::WikipediaBoundingBox, USERNAME, None);
+    let client = client("wikipedia_bounding_box");
     let mut params = HashMap::new();
     params.insert("north", "44.1");
     params.insert("south", "-9.9");
@@ -1314,7 +1328,7 @@ fn call_api_wikipedia_bounding_box() {
 
     let result: WikipediaBoundingBoxResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::WikipediaBoundingBox, Some(params.into())))
         .unwrap();
 
     let expected_result = WikipediaBoundingBoxResponse {
@@ -1340,14 +1354,14 @@ fn call_api_wikipedia_bounding_box() {
 
 #[test]
 fn call_api_wikipedia_search() {
-    let client = ApiClient::new(GeoNamesApi::WikipediaSearch, USERNAME, None);
+    let client = client("wikipedia_search");
     let mut params = HashMap::new();
     params.insert("q", "london");
     params.insert("maxRows", "1");
 
     let result: WikipediaSearchResponse = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(client.call_api(Some(params)))
+        .block_on(client.call(GeoNamesApi::WikipediaSearch, Some(params.into())))
         .unwrap();
 
     let expected_result = WikipediaSearchResponse {
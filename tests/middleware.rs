@@ -0,0 +1,279 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use common::{ocean_params, unused_url, FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiClient, ApiError, GeoNamesApi, Middleware, MiddlewareAction, OceanResponse, Params,
+    RawResponse, RetryPolicy,
+};
+
+// Logs both hooks, so the order of the layers can be checked
+struct Audit {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Middleware for Audit {
+    async fn on_request(
+        &self,
+        api: GeoNamesApi,
+        _params: &mut Params,
+    ) -> Result<MiddlewareAction, ApiError> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{} request {:?}", self.name, api));
+        Ok(MiddlewareAction::Continue)
+    }
+
+    async fn on_response(
+        &self,
+        _api: GeoNamesApi,
+        _params: &Params,
+        response: &RawResponse,
+    ) -> Result<(), ApiError> {
+        self.log.lock().unwrap().push(format!(
+            "{} response {} bytes from {:?}",
+            self.name,
+            response.body.len(),
+            response.host
+        ));
+        Ok(())
+    }
+}
+
+struct Tenant(&'static str);
+
+#[async_trait]
+impl Middleware for Tenant {
+    async fn on_request(
+        &self,
+        _api: GeoNamesApi,
+        params: &mut Params,
+    ) -> Result<MiddlewareAction, ApiError> {
+        params.insert("tenant", self.0);
+        Ok(MiddlewareAction::Continue)
+    }
+}
+
+struct Canned;
+
+#[async_trait]
+impl Middleware for Canned {
+    async fn on_request(
+        &self,
+        _api: GeoNamesApi,
+        _params: &mut Params,
+    ) -> Result<MiddlewareAction, ApiError> {
+        Ok(MiddlewareAction::Respond(Bytes::from_static(
+            OCEAN_BODY.as_bytes(),
+        )))
+    }
+}
+
+struct Reject;
+
+#[async_trait]
+impl Middleware for Reject {
+    async fn on_request(
+        &self,
+        api: GeoNamesApi,
+        _params: &mut Params,
+    ) -> Result<MiddlewareAction, ApiError> {
+        Err(ApiError::InvalidParams(format!(
+            "{:?} is not allowed for this tenant",
+            api
+        )))
+    }
+}
+
+struct RejectEmptyBody;
+
+#[async_trait]
+impl Middleware for RejectEmptyBody {
+    async fn on_response(
+        &self,
+        _api: GeoNamesApi,
+        _params: &Params,
+        response: &RawResponse,
+    ) -> Result<(), ApiError> {
+        if response.body.len() < 10 {
+            return Err(ApiError::GeonamesApi(
+                "Suspiciously short response".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Keeps the responses it sees
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<RawResponse>>>);
+
+#[async_trait]
+impl Middleware for Capture {
+    async fn on_response(
+        &self,
+        _api: GeoNamesApi,
+        _params: &Params,
+        response: &RawResponse,
+    ) -> Result<(), ApiError> {
+        self.0.lock().unwrap().push(response.clone());
+        Ok(())
+    }
+}
+
+#[test]
+fn middleware_injects_params() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .middleware(Tenant("acme"))
+        .build()
+        .unwrap();
+
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    assert_eq!(result.ocean.geoname_id, 3411923);
+    let request = &server.requests()[0];
+    assert!(request.contains("tenant=acme"));
+    assert!(request.contains("lat=40.7834"));
+}
+
+#[test]
+fn layers_run_in_order_and_responses_in_reverse() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let log = Arc::new(Mutex::new(vec![]));
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .middleware(Audit {
+            name: "outer",
+            log: log.clone(),
+        })
+        .middleware(Audit {
+            name: "inner",
+            log: log.clone(),
+        })
+        .build()
+        .unwrap();
+
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    let host = format!("{:?}", Some(server.url.clone()));
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "outer request Ocean".to_string(),
+            "inner request Ocean".to_string(),
+            format!("inner response {} bytes from {}", OCEAN_BODY.len(), host),
+            format!("outer response {} bytes from {}", OCEAN_BODY.len(), host),
+        ]
+    );
+}
+
+#[test]
+fn middleware_short_circuits_with_canned_response() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let client = ApiClient::builder("demo")
+        .base_url(unused_url())
+        .retry_policy(RetryPolicy::none())
+        .middleware(Audit {
+            name: "outer",
+            log: log.clone(),
+        })
+        .middleware(Canned)
+        .middleware(Audit {
+            name: "skipped",
+            log: log.clone(),
+        })
+        .build()
+        .unwrap();
+
+    let response = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(
+            client.call_detailed::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))),
+        )
+        .unwrap();
+
+    assert_eq!(response.data.ocean.name, "North Atlantic Ocean");
+    assert_eq!(response.host, None);
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "outer request Ocean".to_string(),
+            format!("outer response {} bytes from None", OCEAN_BODY.len()),
+        ]
+    );
+}
+
+#[test]
+fn middleware_rejects_request() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .middleware(Reject)
+        .build()
+        .unwrap();
+
+    let result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    match result {
+        Err(ApiError::InvalidParams(message)) => {
+            assert_eq!(message, "Ocean is not allowed for this tenant")
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn middleware_rejects_raw_response() {
+    let server = FixtureServer::start(vec![("/oceanJSON", "{}")]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .middleware(RejectEmptyBody)
+        .build()
+        .unwrap();
+
+    let result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::GeonamesApi(_))));
+}
+
+#[test]
+fn response_hooks_see_status_errors() {
+    let body = r#"{"status":{"message":"user does not exist.","value":10}}"#;
+    let server = FixtureServer::start(vec![("/oceanJSON", body)]);
+    let capture = Capture::default();
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .middleware(capture.clone())
+        .build()
+        .unwrap();
+
+    let result: Result<OceanResponse, _> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::InvalidUser(_))));
+    let seen = capture.0.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].body, body.as_bytes());
+    assert_eq!(seen[0].status, Some(200));
+    assert_eq!(seen[0].host.as_deref(), Some(server.url.as_str()));
+}
@@ -0,0 +1,167 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::StreamExt;
+use geonames_rs::{
+    ApiClient, ApiError, PageOptions, Params, RetryPolicy, SearchRequest, Transport,
+    TransportResponse,
+};
+use reqwest::Url;
+use std::sync::{Arc, Mutex};
+
+// Serves `total` search results, failing on the page that starts at `fail_at`
+#[derive(Clone)]
+struct SearchTransport {
+    total: u32,
+    fail_at: Option<u32>,
+    pages: Arc<Mutex<Vec<(u32, u32)>>>,
+}
+
+impl SearchTransport {
+    fn new(total: u32) -> Self {
+        SearchTransport {
+            total,
+            fail_at: None,
+            pages: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    fn pages(&self) -> Vec<(u32, u32)> {
+        self.pages.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for SearchTransport {
+    async fn get(&self, url: Url) -> Result<TransportResponse, ApiError> {
+        let param = |name: &str| -> u32 {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse().ok())
+                .unwrap()
+        };
+        let (start_row, max_rows) = (param("startRow"), param("maxRows"));
+        self.pages.lock().unwrap().push((start_row, max_rows));
+
+        let body = if self.fail_at == Some(start_row) {
+            r#"{"status":{"message":"database timeout","value":13}}"#.to_string()
+        } else {
+            let geonames: Vec<_> = (start_row..(start_row + max_rows).min(self.total))
+                .map(geoname)
+                .collect();
+            serde_json::json!({ "totalResultsCount": self.total, "geonames": geonames }).to_string()
+        };
+
+        Ok(TransportResponse {
+            status: 200,
+            body: Bytes::from(body),
+        })
+    }
+}
+
+fn geoname(id: u32) -> serde_json::Value {
+    serde_json::json!({
+        "adminCode1": "16", "lng": "13.41", "geonameId": id, "toponymName": "Berlin",
+        "countryId": "2921044", "adminCodes1": { "ISO3166_2": "BE" }, "countryName": "Germany",
+        "fcodeName": "capital of a political entity", "adminName1": "Berlin", "lat": "52.52",
+        "fcode": "PPLC", "fcl": "P", "population": 3426354, "countryCode": "DE",
+        "name": "Berlin", "fclName": "city, village,...",
+    })
+}
+
+fn client(transport: SearchTransport) -> ApiClient {
+    ApiClient::builder("demo")
+        .transport(transport)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn collect_ids(
+    client: &ApiClient,
+    params: impl Into<Params>,
+    options: PageOptions,
+) -> Vec<Result<i32, String>> {
+    tokio::runtime::Runtime::new().unwrap().block_on(
+        client
+            .search_stream(params, options)
+            .map(|item| item.map(|g| g.geoname_id).map_err(|e| format!("{:?}", e)))
+            .collect(),
+    )
+}
+
+#[test]
+fn search_stream_pages_until_total_results_count() {
+    let transport = SearchTransport::new(25);
+    let client = client(transport.clone());
+
+    let ids = collect_ids(
+        &client,
+        Params::new().with("q", "berlin"),
+        PageOptions::default().page_size(10),
+    );
+
+    assert_eq!(ids, (0..25).map(Ok).collect::<Vec<_>>());
+    assert_eq!(transport.pages(), vec![(0, 10), (10, 10), (20, 5)]);
+}
+
+#[test]
+fn search_stream_stops_at_max_items() {
+    let transport = SearchTransport::new(100);
+    let client = client(transport.clone());
+
+    let ids = collect_ids(
+        &client,
+        SearchRequest::new().q("berlin"),
+        PageOptions::default().page_size(10).max_items(15),
+    );
+
+    assert_eq!(ids.len(), 15);
+    assert_eq!(transport.pages(), vec![(0, 10), (10, 5)]);
+}
+
+#[test]
+fn search_stream_respects_row_limit() {
+    let transport = SearchTransport::new(100_000);
+    let client = client(transport.clone());
+
+    let ids = collect_ids(
+        &client,
+        SearchRequest::new().q("berlin").start_row(4500),
+        PageOptions::default().page_size(1000),
+    );
+
+    assert_eq!(ids.len(), 500);
+    assert_eq!(transport.pages(), vec![(4500, 500)]);
+}
+
+#[test]
+fn search_stream_ends_with_the_error() {
+    let mut transport = SearchTransport::new(30);
+    transport.fail_at = Some(10);
+    let client = client(transport.clone());
+
+    let ids = collect_ids(
+        &client,
+        Params::new().with("q", "berlin"),
+        PageOptions::default().page_size(10),
+    );
+
+    assert_eq!(ids.len(), 11);
+    assert!(ids[10].as_ref().unwrap_err().starts_with("DatabaseTimeout"));
+    assert_eq!(transport.pages().len(), 2);
+}
+
+#[test]
+fn search_stream_handles_empty_results() {
+    let transport = SearchTransport::new(0);
+    let client = client(transport.clone());
+
+    let ids = collect_ids(
+        &client,
+        Params::new().with("q", "nowhere"),
+        PageOptions::default(),
+    );
+
+    assert!(ids.is_empty());
+    assert_eq!(transport.pages(), vec![(0, 100)]);
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use common::{FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiClient, ApiError, FindNearbyResponse, GeoNamesApi, OceanResponse, Params, SearchResponse,
+};
+use std::collections::HashMap;
+
+#[test]
+fn params_from_display_values() {
+    let lat: f64 = 47.03;
+    let mut params = Params::new();
+    params
+        .insert("lat", lat)
+        .insert("lng", 10.02)
+        .insert("maxRows", 10)
+        .insert("isReduced", true)
+        .insert(String::from("country"), String::from("AT"));
+
+    assert_eq!(params.get("lat"), Some("47.03"));
+    assert_eq!(params.get("lng"), Some("10.02"));
+    assert_eq!(params.get("maxRows"), Some("10"));
+    assert_eq!(params.get("isReduced"), Some("true"));
+    assert_eq!(params.get("country"), Some("AT"));
+    assert_eq!(params.len(), 5);
+}
+
+#[test]
+fn params_from_collections() {
+    let mut map = HashMap::new();
+    map.insert("lat", "47.03");
+    map.insert("lng", "10.02");
+
+    let from_map = Params::from(map);
+    let from_array = Params::from([("lat", "47.03"), ("lng", "10.02")]);
+    let collected: Params = vec![("lng", 10.02), ("lat", 47.03)].into_iter().collect();
+
+    assert_eq!(from_map, from_array);
+    assert_eq!(from_map, collected);
+}
+
+#[test]
+fn call_with_runtime_params_and_credentials() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let username = format!("{}-{}", "demo", 1);
+    let token = String::from("secret");
+    let client = ApiClient::builder(username)
+        .token(token)
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+
+    let (lat, lng): (f64, f64) = (40.7834, -43.96635);
+    let params = Params::new().with("lat", lat).with("lng", lng);
+
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(params)))
+        .unwrap();
+
+    assert_eq!(result.ocean.geoname_id, 3411923);
+    let request = &server.requests()[0];
+    assert!(request.contains("username=demo-1"));
+    assert!(request.contains("token=secret"));
+}
+
+#[test]
+fn params_with_repeated_keys() {
+    let mut params = Params::new().with_all("featureCode", ["PPLA", "PPLC"]);
+    params.append("country", "DE").append("country", "AT");
+
+    assert_eq!(params.get("featureCode"), Some("PPLA"));
+    assert_eq!(params.get_all("featureCode"), ["PPLA", "PPLC"]);
+    assert_eq!(params.get_all("country"), ["DE", "AT"]);
+    assert!(params.get_all("lang").is_empty());
+
+    params.insert("country", "CH");
+    assert_eq!(params.get_all("country"), ["CH"]);
+
+    let collected: Params = vec![("country", "DE"), ("country", "AT")]
+        .into_iter()
+        .collect();
+    assert_eq!(collected.get_all("country"), ["DE", "AT"]);
+}
+
+#[test]
+fn call_with_repeated_params() {
+    let server = FixtureServer::start(vec![(
+        "/searchJSON",
+        r#"{"totalResultsCount":0,"geonames":[]}"#,
+    )]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+    let params = Params::new()
+        .with("q", "berlin")
+        .with_all("featureCode", ["PPLA", "PPLC"])
+        .with_all("country", ["DE", "AT"]);
+
+    let result: SearchResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Search, Some(params)))
+        .unwrap();
+
+    assert_eq!(result.total_results_count, 0);
+    let request = &server.requests()[0];
+    assert!(request.contains("country=DE&country=AT"));
+    assert!(request.contains("featureCode=PPLA&featureCode=PPLC"));
+}
+
+#[test]
+fn call_validates_every_repeated_value() {
+    let server = FixtureServer::start(vec![]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+    let params = Params::new()
+        .with("lat", 47.3)
+        .with("lng", 9.0)
+        .with_all("style", ["FULL", "HUGE"]);
+
+    let result: Result<FindNearbyResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::FindNearby, Some(params)));
+
+    match result {
+        Err(ApiError::InvalidParams(message)) => assert!(message.contains("HUGE")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use common::{ocean_params, FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiClient, ApiError, CreditBudget, GeoNamesApi, LimitBehavior, OceanResponse, Params,
+    RetryPolicy,
+};
+use std::time::{Duration, Instant};
+
+const HOURLY_LIMIT: &str = r#"{"status":{"message":"the hourly limit of 1000 credits for demo has been exceeded.","value":19}}"#;
+
+fn client(server: &FixtureServer, budget: CreditBudget) -> ApiClient {
+    ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .retry_policy(RetryPolicy::none())
+        .credit_budget(budget)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn credits_for_uses_cost_table() {
+    let budget = CreditBudget::default().cost(GeoNamesApi::Ocean, 3);
+    let full = Params::new().with("q", "Berlin").with("style", "FULL");
+    let short = Params::new().with("q", "Berlin").with("style", "SHORT");
+
+    assert_eq!(budget.credits_for(GeoNamesApi::Ocean, None), 3);
+    assert_eq!(budget.credits_for(GeoNamesApi::Timezone, None), 1);
+    assert_eq!(
+        budget.credits_for(GeoNamesApi::FindNearbyStreetsOsm, None),
+        2
+    );
+    assert_eq!(budget.credits_for(GeoNamesApi::Search, Some(&full)), 2);
+    assert_eq!(budget.credits_for(GeoNamesApi::Search, Some(&short)), 1);
+}
+
+#[test]
+fn call_counts_credits_used() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let client = client(&server, CreditBudget::default().cost(GeoNamesApi::Ocean, 2));
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..3 {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+            .unwrap();
+    }
+
+    let usage = client.credit_usage().unwrap();
+    assert_eq!(usage.credits_used, 6);
+    assert_eq!(usage.requests, 3);
+    assert_eq!(usage.per_api.get(&GeoNamesApi::Ocean), Some(&6));
+
+    let remaining: Vec<u64> = client.remaining_credits().iter().map(|r| r.1).collect();
+    assert_eq!(remaining, vec![994, 19994]);
+}
+
+#[test]
+fn fail_fast_returns_rate_limited_without_sending() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let budget = CreditBudget::new()
+        .limit(2, Duration::from_secs(60))
+        .behavior(LimitBehavior::FailFast);
+    let client = client(&server, budget);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..2 {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+            .unwrap();
+    }
+    let result: Result<OceanResponse, ApiError> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::RateLimited(wait)) if wait > Duration::ZERO));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn window_too_long_for_a_wait_fails_fast_with_max_wait() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let budget = CreditBudget::new()
+        .limit(1, Duration::MAX)
+        .behavior(LimitBehavior::FailFast);
+    let client = client(&server, budget);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+    let result: Result<OceanResponse, ApiError> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::RateLimited(wait)) if wait == Duration::MAX));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn wait_blocks_until_credits_refill() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let budget = CreditBudget::new().limit(1, Duration::from_millis(200));
+    let client = client(&server, budget);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let started = Instant::now();
+    for _ in 0..2 {
+        let _: OceanResponse = runtime
+            .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+            .unwrap();
+    }
+
+    assert!(started.elapsed() >= Duration::from_millis(150));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn cloned_clients_share_the_budget() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let budget = CreditBudget::new()
+        .limit(1, Duration::from_secs(60))
+        .behavior(LimitBehavior::FailFast);
+    let client = client(&server, budget);
+    let other = client.clone();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let _: OceanResponse = runtime
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+    let result: Result<OceanResponse, ApiError> =
+        runtime.block_on(other.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::RateLimited(_))));
+}
+
+#[test]
+fn hourly_limit_error_exhausts_the_hourly_budget() {
+    let server = FixtureServer::start(vec![("/oceanJSON", HOURLY_LIMIT)]);
+    let budget = CreditBudget::default().behavior(LimitBehavior::FailFast);
+    let client = client(&server, budget);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let first: Result<OceanResponse, ApiError> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+    let second: Result<OceanResponse, ApiError> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(first, Err(ApiError::HourlyLimitExceeded(_))));
+    assert!(matches!(second, Err(ApiError::RateLimited(_))));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn client_without_budget_has_no_usage() {
+    let client = ApiClient::new("demo", None);
+
+    assert!(client.credit_usage().is_none());
+    assert!(client.remaining_credits().is_empty());
+}
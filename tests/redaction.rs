@@ -0,0 +1,153 @@
+mod common;
+
+use std::error::Error;
+
+use async_trait::async_trait;
+use common::{ocean_params, unused_url, FixtureServer};
+use geonames_rs::{
+    ApiClient, ApiError, GeoNamesApi, OceanResponse, RequestError, RetryPolicy, Transport,
+    TransportResponse,
+};
+use reqwest::Url;
+
+const USERNAME: &str = "alice_geo";
+const TOKEN: &str = "tok3n-s3cr3t";
+
+fn call(client: &ApiClient) -> ApiError {
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap_err()
+}
+
+// Display, Debug and every source in the chain
+fn assert_redacted(error: &ApiError) {
+    let mut texts = vec![error.to_string(), format!("{:?}", error)];
+    let mut source = error.source();
+    while let Some(error) = source {
+        texts.push(error.to_string());
+        texts.push(format!("{:?}", error));
+        source = error.source();
+    }
+
+    for text in texts {
+        assert!(!text.contains(TOKEN), "token leaked: {}", text);
+        assert!(!text.contains(USERNAME), "username leaked: {}", text);
+    }
+}
+
+fn fixture_client(body: &'static str, status: u16) -> ApiClient {
+    let server = FixtureServer::start_with_responses(vec![("/oceanJSON", vec![(status, body)])]);
+    ApiClient::builder(USERNAME)
+        .token(TOKEN)
+        .base_url(server.url)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+struct EchoTransport;
+
+#[async_trait]
+impl Transport for EchoTransport {
+    async fn get(&self, url: Url) -> Result<TransportResponse, ApiError> {
+        Err(ApiError::Connection(RequestError::new(format!(
+            "Failed to reach {}",
+            url
+        ))))
+    }
+}
+
+#[test]
+fn connection_error_is_redacted() {
+    let client = ApiClient::builder(USERNAME)
+        .token(TOKEN)
+        .base_url(unused_url())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let error = call(&client);
+
+    assert!(matches!(error, ApiError::Connection(_)));
+    assert!(error.source().is_some());
+    assert_redacted(&error);
+}
+
+#[test]
+fn custom_transport_error_is_redacted() {
+    let client = ApiClient::builder(USERNAME)
+        .token(TOKEN)
+        .transport(EchoTransport)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let error = call(&client);
+
+    assert!(error.to_string().contains("token=REDACTED"));
+    assert_redacted(&error);
+}
+
+#[test]
+fn status_message_is_redacted() {
+    let error = call(&fixture_client(
+        r#"{"status":{"message":"the hourly limit of 1000 credits for alice_geo has been exceeded.","value":19}}"#,
+        200,
+    ));
+
+    assert!(matches!(error, ApiError::HourlyLimitExceeded(_)));
+    assert_eq!(
+        error.status().unwrap().message,
+        "the hourly limit of 1000 credits for REDACTED has been exceeded."
+    );
+    assert_redacted(&error);
+}
+
+#[test]
+fn body_snippets_are_redacted() {
+    let deserialization = call(&fixture_client(
+        r#"{"unexpected":"tok3n-s3cr3t","user":"alice_geo"}"#,
+        200,
+    ));
+    let server_error = call(&fixture_client("token tok3n-s3cr3t rejected", 503));
+
+    assert!(matches!(deserialization, ApiError::Deserialization(_)));
+    assert!(deserialization.body_snippet().unwrap().contains("REDACTED"));
+    assert_redacted(&deserialization);
+    assert!(matches!(server_error, ApiError::HttpStatus(_)));
+    assert_redacted(&server_error);
+}
+
+#[test]
+fn username_is_redacted_as_whole_word() {
+    let server = FixtureServer::start(vec![(
+        "/oceanJSON",
+        r#"{"status":{"message":"user geo is not enabled for geonames","value":10}}"#,
+    )]);
+    let client = ApiClient::builder("geo")
+        .base_url(server.url)
+        .build()
+        .unwrap();
+
+    let error = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap_err();
+
+    assert_eq!(
+        error.status().unwrap().message,
+        "user REDACTED is not enabled for geonames"
+    );
+}
+
+#[test]
+fn client_debug_is_redacted() {
+    let client = ApiClient::builder(USERNAME).token(TOKEN).build().unwrap();
+
+    let debug = format!("{:?}", client);
+
+    assert!(!debug.contains(TOKEN));
+    assert!(!debug.contains(USERNAME));
+    assert!(debug.contains("secure.geonames.net"));
+}
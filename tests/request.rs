@@ -0,0 +1,115 @@
+mod common;
+
+use common::{FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiClient, ApiEndpoint, Cities, Endpoint, FindNearestIntersectionOsmRequest, GeoNamesApi,
+    GeoNamesRequest, NeighboursRequest, OceanRequest, Operator, Params,
+    PostalCodeCountryInfoRequest, SearchRequest, Style, TimezoneRequest,
+};
+
+#[test]
+fn request_into_params() {
+    let params = SearchRequest::new()
+        .name_starts_with("Ber")
+        .country("DE")
+        .country("AT")
+        .feature_class("P")
+        .cities(Cities::Cities15000)
+        .style(Style::Full)
+        .operator(Operator::Or)
+        .is_name_required(true)
+        .max_rows(50)
+        .into_params()
+        .unwrap();
+
+    assert_eq!(params.get("name_startsWith"), Some("Ber"));
+    assert_eq!(params.get_all("country"), ["DE", "AT"]);
+    assert_eq!(params.get("featureClass"), Some("P"));
+    assert_eq!(params.get("cities"), Some("cities15000"));
+    assert_eq!(params.get("style"), Some("FULL"));
+    assert_eq!(params.get("operator"), Some("OR"));
+    assert_eq!(params.get("isNameRequired"), Some("true"));
+    assert_eq!(params.get("maxRows"), Some("50"));
+}
+
+#[test]
+fn request_required_params() {
+    let params = TimezoneRequest::new(50.01, 10.2)
+        .lang("de")
+        .into_params()
+        .unwrap();
+    assert_eq!(
+        params,
+        Params::from([("lat", "50.01"), ("lng", "10.2"), ("lang", "de")])
+    );
+
+    let params = NeighboursRequest::for_country("DE").into_params().unwrap();
+    assert_eq!(params, Params::from([("country", "DE")]));
+
+    let params = FindNearestIntersectionOsmRequest::new(37.451, -122.18)
+        .into_params()
+        .unwrap();
+    assert_eq!(
+        params,
+        Params::from([("lat", "37.451"), ("lng", "-122.18")])
+    );
+    assert!(GeoNamesApi::FindNearestIntersectionOsm
+        .allowed_params()
+        .unwrap()
+        .validate(&params)
+        .is_ok());
+
+    assert_eq!(PostalCodeCountryInfoRequest::new().into_params(), None);
+}
+
+fn api_of<R: GeoNamesRequest>(_: &R) -> GeoNamesApi {
+    <R::Endpoint as Endpoint>::API
+}
+
+#[test]
+fn request_bound_to_endpoint() {
+    assert_eq!(api_of(&OceanRequest::new(0.0, 0.0)), GeoNamesApi::Ocean);
+    assert_eq!(api_of(&SearchRequest::new()), GeoNamesApi::Search);
+    assert_eq!(
+        api_of(&TimezoneRequest::new(0.0, 0.0)),
+        GeoNamesApi::Timezone
+    );
+}
+
+#[test]
+fn send_typed_request() {
+    let server = FixtureServer::start(vec![
+        ("/oceanJSON", OCEAN_BODY),
+        ("/searchJSON", r#"{"totalResultsCount":0,"geonames":[]}"#),
+    ]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let ocean = runtime
+        .block_on(client.send(OceanRequest::new(40.7834, -43.96635).radius(10.0)))
+        .unwrap();
+    assert_eq!(ocean.ocean.name, "North Atlantic Ocean");
+
+    let search = runtime
+        .block_on(
+            client.send(
+                SearchRequest::new()
+                    .q("berlin")
+                    .country("DE")
+                    .feature_code("PPLC")
+                    .style(Style::Short)
+                    .start_row(0)
+                    .max_rows(10),
+            ),
+        )
+        .unwrap();
+    assert_eq!(search.total_results_count, 0);
+
+    let requests = server.requests();
+    assert!(requests[0].starts_with("/oceanJSON?"));
+    assert!(requests[0].contains("radius=10"));
+    assert!(requests[1].starts_with("/searchJSON?"));
+}
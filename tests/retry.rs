@@ -0,0 +1,127 @@
+mod common;
+
+use common::{ocean_params, unused_url, FixtureServer, OCEAN_BODY};
+use geonames_rs::{ApiClient, ApiError, GeoNamesApi, OceanResponse, RetryPolicy, RetryableError};
+use std::time::Duration;
+
+const DATABASE_TIMEOUT: &str = r#"{"status":{"message":"database timeout","value":13}}"#;
+const SERVER_OVERLOADED: &str = r#"{"status":{"message":"server overloaded","value":22}}"#;
+const INVALID_USER: &str = r#"{"status":{"message":"user does not exist.","value":10}}"#;
+
+fn fast_retries(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::default()
+        .max_attempts(max_attempts)
+        .backoff(Duration::from_millis(1), Duration::from_millis(5))
+}
+
+fn client(server: &FixtureServer, retry_policy: RetryPolicy) -> ApiClient {
+    ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .retry_policy(retry_policy)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn call_retries_transient_errors() {
+    let server = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![
+            (200, DATABASE_TIMEOUT),
+            (503, "Service Unavailable"),
+            (200, SERVER_OVERLOADED),
+            (200, OCEAN_BODY),
+        ],
+    )]);
+    let client = client(&server, fast_retries(4));
+
+    let result: OceanResponse = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    assert_eq!(result.ocean.geoname_id, 3411923);
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[test]
+fn call_gives_up_after_max_attempts() {
+    let server =
+        FixtureServer::start_with_responses(vec![("/oceanJSON", vec![(200, DATABASE_TIMEOUT)])]);
+    let client = client(&server, fast_retries(3));
+
+    let result: Result<OceanResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::DatabaseTimeout(_))));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn call_never_retries_permanent_errors() {
+    let server =
+        FixtureServer::start_with_responses(vec![("/oceanJSON", vec![(200, INVALID_USER)])]);
+    let client = client(&server, fast_retries(5));
+
+    let result: Result<OceanResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::InvalidUser(_))));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn call_retries_only_configured_kinds() {
+    let server = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![(200, SERVER_OVERLOADED), (200, OCEAN_BODY)],
+    )]);
+    let client = client(
+        &server,
+        fast_retries(3).retry_on(vec![RetryableError::DatabaseTimeout]),
+    );
+
+    let result: Result<OceanResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::ServerOverloaded(_))));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn call_without_retries() {
+    let client = ApiClient::builder("demo")
+        .base_url(unused_url())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let result: Result<OceanResponse, ApiError> = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(matches!(result, Err(ApiError::Connection(_))));
+}
+
+#[test]
+fn retry_delay_backs_off_exponentially() {
+    let policy = RetryPolicy::default()
+        .backoff(Duration::from_millis(100), Duration::from_millis(1000))
+        .jitter(false);
+
+    assert_eq!(policy.delay(1), Duration::from_millis(100));
+    assert_eq!(policy.delay(2), Duration::from_millis(200));
+    assert_eq!(policy.delay(3), Duration::from_millis(400));
+    assert_eq!(policy.delay(10), Duration::from_millis(1000));
+    assert_eq!(policy.delay(u32::MAX), Duration::from_millis(1000));
+
+    let jittered = policy.jitter(true);
+    for attempt in 1..5 {
+        let delay = jittered.delay(attempt);
+        assert!(delay >= Duration::from_millis(50));
+        assert!(delay <= Duration::from_millis(1000));
+    }
+}
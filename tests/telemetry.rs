@@ -0,0 +1,273 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::{ocean_params, FixtureServer, OCEAN_BODY};
+use geonames_rs::{
+    ApiClient, CacheConfig, Counter, GeoNamesApi, Histogram, MetricsRecorder, OceanResponse,
+    RetryPolicy,
+};
+
+#[derive(Clone, Default)]
+struct Recorder {
+    counters: Arc<Mutex<Vec<(Counter, GeoNamesApi, u64)>>>,
+    histograms: Arc<Mutex<Vec<(Histogram, GeoNamesApi, f64)>>>,
+}
+
+impl Recorder {
+    fn counter(&self, counter: Counter) -> u64 {
+        self.counters
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(c, api, _)| *c == counter && *api == GeoNamesApi::Ocean)
+            .map(|(_, _, value)| value)
+            .sum()
+    }
+
+    fn histogram(&self, histogram: Histogram) -> Vec<f64> {
+        self.histograms
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(h, _, _)| *h == histogram)
+            .map(|(_, _, value)| *value)
+            .collect()
+    }
+}
+
+impl MetricsRecorder for Recorder {
+    fn increment_counter(&self, counter: Counter, api: GeoNamesApi, value: u64) {
+        self.counters.lock().unwrap().push((counter, api, value));
+    }
+
+    fn record_histogram(&self, histogram: Histogram, api: GeoNamesApi, value: f64) {
+        self.histograms
+            .lock()
+            .unwrap()
+            .push((histogram, api, value));
+    }
+}
+
+#[test]
+fn call_records_metrics() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let recorder = Recorder::default();
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .metrics_recorder(recorder.clone())
+        .build()
+        .unwrap();
+
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+        .unwrap();
+
+    assert_eq!(recorder.counter(Counter::Calls), 1);
+    assert_eq!(recorder.counter(Counter::CallErrors), 0);
+    assert_eq!(recorder.counter(Counter::HttpRequests), 1);
+    assert_eq!(recorder.counter(Counter::Credits), 1);
+    assert_eq!(recorder.histogram(Histogram::CallDuration).len(), 1);
+    assert_eq!(recorder.histogram(Histogram::HttpDuration).len(), 1);
+    assert_eq!(
+        recorder.histogram(Histogram::ResponseBytes),
+        vec![OCEAN_BODY.len() as f64]
+    );
+}
+
+#[test]
+fn cache_hits_and_misses_are_counted() {
+    let server = FixtureServer::start(vec![("/oceanJSON", OCEAN_BODY)]);
+    let recorder = Recorder::default();
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .cache(CacheConfig::new(10))
+        .metrics_recorder(recorder.clone())
+        .build()
+        .unwrap();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    for _ in 0..3 {
+        rt.block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))))
+            .unwrap();
+    }
+
+    assert_eq!(recorder.counter(Counter::Calls), 3);
+    assert_eq!(recorder.counter(Counter::CacheMisses), 1);
+    assert_eq!(recorder.counter(Counter::CacheHits), 2);
+    assert_eq!(recorder.counter(Counter::HttpRequests), 1);
+    assert_eq!(recorder.counter(Counter::Credits), 1);
+}
+
+#[test]
+fn retries_and_errors_are_counted() {
+    let server = FixtureServer::start_with_responses(vec![(
+        "/oceanJSON",
+        vec![
+            (503, "unavailable"),
+            (
+                200,
+                r#"{"status":{"message":"user does not exist.","value":10}}"#,
+            ),
+        ],
+    )]);
+    let recorder = Recorder::default();
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .retry_policy(
+            RetryPolicy::default()
+                .backoff(Duration::from_millis(1), Duration::from_millis(1))
+                .jitter(false),
+        )
+        .metrics_recorder(recorder.clone())
+        .build()
+        .unwrap();
+
+    let result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))));
+
+    assert!(result.is_err());
+    assert_eq!(recorder.counter(Counter::Retries), 1);
+    assert_eq!(recorder.counter(Counter::HttpRequests), 2);
+    // The server error is not billed, the status answer is
+    assert_eq!(recorder.counter(Counter::Credits), 1);
+    assert_eq!(recorder.counter(Counter::Calls), 1);
+    assert_eq!(recorder.counter(Counter::CallErrors), 1);
+}
+
+#[test]
+fn metric_names() {
+    assert_eq!(Counter::Calls.name(), "geonames_calls_total");
+    assert_eq!(Counter::Credits.name(), "geonames_credits_total");
+    assert_eq!(
+        Histogram::CallDuration.name(),
+        "geonames_call_duration_seconds"
+    );
+}
+
+#[cfg(feature = "tracing")]
+mod spans {
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+    use tracing_core::span::Current;
+
+    use super::*;
+
+    // Collects every span field and event field, with a stack of entered spans for Span::current
+    #[derive(Clone, Default)]
+    struct Capture {
+        fields: Arc<Mutex<HashMap<String, String>>>,
+        events: Arc<Mutex<Vec<String>>>,
+        spans: Arc<Mutex<HashMap<u64, &'static Metadata<'static>>>>,
+        stack: Arc<Mutex<Vec<u64>>>,
+        next_id: Arc<AtomicU64>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+            self.spans.lock().unwrap().insert(id, span.metadata());
+            span.record(&mut FieldVisitor(&mut self.fields.lock().unwrap()));
+            Id::from_u64(id)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            values.record(&mut FieldVisitor(&mut self.fields.lock().unwrap()));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = HashMap::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.events.lock().unwrap().push(format!("{:?}", fields));
+        }
+
+        fn enter(&self, span: &Id) {
+            self.stack.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.stack.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> Current {
+            match self.stack.lock().unwrap().last() {
+                Some(id) => Current::new(Id::from_u64(*id), self.spans.lock().unwrap()[id]),
+                None => Current::none(),
+            }
+        }
+    }
+
+    #[test]
+    fn call_span_records_request_details() {
+        let server = FixtureServer::start_with_responses(vec![(
+            "/oceanJSON",
+            vec![(503, "unavailable"), (200, OCEAN_BODY)],
+        )]);
+        let client = ApiClient::builder("alice_geo")
+            .token("tok3n-s3cr3t")
+            .base_url(server.url.clone())
+            .cache(CacheConfig::new(10))
+            .retry_policy(
+                RetryPolicy::default()
+                    .backoff(Duration::from_millis(1), Duration::from_millis(1))
+                    .jitter(false),
+            )
+            .build()
+            .unwrap();
+        let capture = Capture::default();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        tracing::subscriber::with_default(capture.clone(), || {
+            rt.block_on(
+                client.call::<OceanResponse>(GeoNamesApi::Ocean, Some(ocean_params(40.7834))),
+            )
+            .unwrap()
+        });
+
+        let fields = capture.fields.lock().unwrap().clone();
+        assert_eq!(fields["endpoint"], "ocean");
+        assert!(fields["params"].contains("lat=40.7834"));
+        assert_eq!(fields["cache"], "miss");
+        assert_eq!(fields["host"], server.url);
+        assert_eq!(fields["http_status"], "200");
+        assert_eq!(fields["response_bytes"], OCEAN_BODY.len().to_string());
+        assert_eq!(fields["attempts"], "2");
+        assert_eq!(fields["credits"], "1");
+        assert!(fields.contains_key("latency_ms"));
+
+        let events = capture.events.lock().unwrap().join("\n");
+        assert!(events.contains("Retrying GeoNames request"));
+
+        let everything = format!("{:?}\n{}", fields, events);
+        assert!(!everything.contains("tok3n-s3cr3t"));
+        assert!(!everything.contains("alice_geo"));
+    }
+}
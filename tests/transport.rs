@@ -0,0 +1,116 @@
+mod common;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use common::OCEAN_BODY;
+use geonames_rs::{
+    ApiClient, ApiError, GeoNamesApi, OceanRequest, OceanResponse, Params, RequestError,
+    RetryPolicy, Transport, TransportResponse,
+};
+use reqwest::Url;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct FakeTransport {
+    responses: HashMap<&'static str, (u16, &'static str)>,
+    urls: Arc<Mutex<Vec<Url>>>,
+}
+
+impl FakeTransport {
+    fn with(mut self, path: &'static str, status: u16, body: &'static str) -> Self {
+        self.responses.insert(path, (status, body));
+        self
+    }
+
+    fn urls(&self) -> Vec<Url> {
+        self.urls.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for FakeTransport {
+    async fn get(&self, url: Url) -> Result<TransportResponse, ApiError> {
+        self.urls.lock().unwrap().push(url.clone());
+
+        match self.responses.get(url.path()) {
+            Some((status, body)) => Ok(TransportResponse {
+                status: *status,
+                body: Bytes::from_static(body.as_bytes()),
+            }),
+            None => Err(ApiError::Connection(RequestError::new(format!(
+                "No response for {}",
+                url
+            )))),
+        }
+    }
+}
+
+fn client(transport: FakeTransport) -> ApiClient {
+    ApiClient::builder("demo")
+        .transport(transport)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn call_uses_custom_transport() {
+    let transport = FakeTransport::default().with("/oceanJSON", 200, OCEAN_BODY);
+    let client = client(transport.clone());
+
+    let result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(client.send(OceanRequest::new(40.7834, -43.96635)))
+        .unwrap();
+
+    assert_eq!(result.ocean.name, "North Atlantic Ocean");
+
+    let urls = transport.urls();
+    assert_eq!(urls.len(), 1);
+    assert_eq!(urls[0].host_str(), Some("secure.geonames.org"));
+    assert_eq!(
+        urls[0].query(),
+        Some("lat=40.7834&lng=-43.96635&username=demo")
+    );
+}
+
+#[test]
+fn call_maps_status_body_from_transport() {
+    let transport = FakeTransport::default().with(
+        "/oceanJSON",
+        200,
+        r#"{"status":{"message":"user does not exist.","value":10}}"#,
+    );
+    let client = client(transport);
+
+    let result: Result<OceanResponse, ApiError> =
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(client.call(
+                GeoNamesApi::Ocean,
+                Some(Params::new().with("lat", 1).with("lng", 2)),
+            ));
+
+    assert!(matches!(result, Err(ApiError::InvalidUser(_))));
+}
+
+#[test]
+fn call_returns_transport_errors() {
+    let transport = FakeTransport::default().with("/oceanJSON", 503, "Service Unavailable");
+    let client = client(transport);
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let unavailable: Result<OceanResponse, ApiError> = runtime.block_on(client.call(
+        GeoNamesApi::Ocean,
+        Some(Params::new().with("lat", 1).with("lng", 2)),
+    ));
+    let unknown: Result<serde_json::Value, ApiError> = runtime.block_on(client.call(
+        GeoNamesApi::Timezone,
+        Some(Params::new().with("lat", 1).with("lng", 2)),
+    ));
+
+    assert!(matches!(unavailable, Err(ApiError::HttpStatus(_))));
+    assert_eq!(unavailable.unwrap_err().http_status(), Some(503));
+    assert!(matches!(unknown, Err(ApiError::Connection(_))));
+}
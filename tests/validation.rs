@@ -0,0 +1,171 @@
+mod common;
+
+use common::FixtureServer;
+use geonames_rs::{
+    ApiClient, ApiEndpoint, ApiError, GeoNamesApi, OceanResponse, ParamKind, ParamSpec, Params,
+};
+
+fn validate(api: GeoNamesApi, params: Params) -> Result<(), String> {
+    match api.allowed_params().unwrap().validate(&params) {
+        Ok(()) => Ok(()),
+        Err(ApiError::InvalidParams(message)) => Err(message),
+        Err(other) => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn validate_required_params() {
+    assert_eq!(
+        validate(GeoNamesApi::Ocean, Params::new().with("lat", 40.78)),
+        Err("Missing required param 'lng'".to_string())
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Get, Params::new()),
+        Err("Missing required param 'geonameId'".to_string())
+    );
+    assert!(validate(
+        GeoNamesApi::Ocean,
+        Params::new().with("lat", 40.78).with("lng", -43.96)
+    )
+    .is_ok());
+    assert_eq!(
+        validate(GeoNamesApi::FindNearestIntersectionOsm, Params::new()),
+        Err("Missing required param 'lat'".to_string())
+    );
+    assert!(validate(
+        GeoNamesApi::FindNearestIntersectionOsm,
+        Params::new().with("lat", 37.451).with("lng", -122.18)
+    )
+    .is_ok());
+}
+
+#[test]
+fn validate_value_kinds() {
+    let ocean = |lat: &str| Params::new().with("lat", lat).with("lng", 10.2);
+
+    assert_eq!(
+        validate(GeoNamesApi::Ocean, ocean("95")),
+        Err("Value '95' for param 'lat' is out of range: must be between -90 and 90".to_string())
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Ocean, ocean("north")),
+        Err("Invalid value 'north' for param 'lat': expected a number".to_string())
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Ocean, ocean("NaN")),
+        Err("Invalid value 'NaN' for param 'lat': expected a finite number".to_string())
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Search, Params::new().with("maxRows", "ten")),
+        Err("Invalid value 'ten' for param 'maxRows': expected an integer".to_string())
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Search, Params::new().with("maxRows", -1)),
+        Err("Value '-1' for param 'maxRows' is out of range: must be at least 0".to_string())
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Search, Params::new().with("inclBbox", "yes")),
+        Err("Invalid value 'yes' for param 'inclBbox': expected 'true' or 'false'".to_string())
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Search, Params::new().with("country", "DEU")),
+        Err(
+            "Invalid value 'DEU' for param 'country': expected an ISO 3166-1 alpha-2 country code"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        validate(
+            GeoNamesApi::Timezone,
+            Params::new()
+                .with("lat", 50.01)
+                .with("lng", 10.2)
+                .with("date", "22.12.2023")
+        ),
+        Err(
+            "Invalid value '22.12.2023' for param 'date': expected a date formatted as yyyy-MM-dd"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Search, Params::new().with("style", "HUGE")),
+        Err("Invalid value 'HUGE' for param 'style': expected one of 'SHORT', 'MEDIUM', 'LONG', 'FULL'".to_string())
+    );
+    assert!(validate(
+        GeoNamesApi::Search,
+        Params::new()
+            .with("q", "berlin")
+            .with("country", "DE")
+            .with("maxRows", 10)
+            .with("fuzzy", 0.8)
+            .with("isNameRequired", true)
+    )
+    .is_ok());
+}
+
+#[test]
+fn validate_param_groups() {
+    assert_eq!(
+        validate(
+            GeoNamesApi::Neighbours,
+            Params::new()
+                .with("geonameId", 2658434)
+                .with("country", "CH")
+        ),
+        Err("Params 'geonameId', 'country' are mutually exclusive".to_string())
+    );
+    assert_eq!(
+        validate(GeoNamesApi::Neighbours, Params::new()),
+        Err("One of the params 'geonameId', 'country' is required".to_string())
+    );
+    assert_eq!(
+        validate(
+            GeoNamesApi::FindNearbyPostalCodes,
+            Params::new().with("lat", 47)
+        ),
+        Err("Params 'lat', 'lng' must be set together".to_string())
+    );
+    assert!(validate(
+        GeoNamesApi::FindNearbyPostalCodes,
+        Params::new()
+            .with("postalCode", "8775")
+            .with("country", "CH")
+    )
+    .is_ok());
+}
+
+#[test]
+fn param_metadata() {
+    let table = GeoNamesApi::Ocean.allowed_params().unwrap();
+
+    assert_eq!(
+        table.get("lat"),
+        Some(&ParamSpec::required(ParamKind::LATITUDE))
+    );
+    assert_eq!(
+        table.get("radius"),
+        Some(&ParamSpec::optional(ParamKind::RADIUS))
+    );
+    assert_eq!(table.get("maxRows"), None);
+}
+
+#[test]
+fn call_rejects_invalid_params_before_request() {
+    let server = FixtureServer::start(vec![]);
+    let client = ApiClient::builder("demo")
+        .base_url(server.url.clone())
+        .build()
+        .unwrap();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let missing: Result<OceanResponse, ApiError> =
+        runtime.block_on(client.call(GeoNamesApi::Ocean, None));
+    let out_of_range: Result<OceanResponse, ApiError> = runtime.block_on(client.call(
+        GeoNamesApi::Ocean,
+        Some(Params::new().with("lat", 40.78).with("lng", 200)),
+    ));
+
+    assert!(matches!(missing, Err(ApiError::InvalidParams(_))));
+    assert!(matches!(out_of_range, Err(ApiError::InvalidParams(_))));
+    assert!(server.requests().is_empty());
+}